@@ -1,6 +1,176 @@
+use std::fmt::{self, Display, Formatter};
+
 pub mod request;
 pub mod response;
 
+/// an error encountered while decoding an htmx header, describing why the
+/// value was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeError {
+    reason: &'static str,
+}
+
+impl DecodeError {
+    pub(crate) const fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+
+    /// why the value was rejected.
+    #[must_use]
+    pub const fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid header value: {}", self.reason)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl From<DecodeError> for headers_core::Error {
+    fn from(_: DecodeError) -> Self {
+        Self::invalid()
+    }
+}
+
+/// typed access to htmx headers in an [`http::HeaderMap`].
+pub trait HeaderMapExt {
+    /// decode the header `H`.
+    ///
+    /// returns `None` if the header is absent or invalid.
+    fn typed_get<H: headers_core::Header>(&self) -> Option<H>;
+
+    /// encode `header`, replacing any existing values of it.
+    ///
+    /// NOTE: Panics if [`headers_core::Header::encode`] does.
+    fn typed_insert<H: headers_core::Header>(&mut self, header: H);
+
+    /// remove all values of the header `H`, returning them decoded.
+    ///
+    /// returns `None` if the header was absent or invalid. the header is
+    /// removed either way.
+    fn remove_htmx<H: headers_core::Header>(&mut self) -> Option<H>;
+}
+
+impl HeaderMapExt for http::HeaderMap {
+    fn typed_get<H: headers_core::Header>(&self) -> Option<H> {
+        decode_optional(self).ok().flatten()
+    }
+
+    fn typed_insert<H: headers_core::Header>(&mut self, header: H) {
+        insert_encoded(self, &header);
+    }
+
+    fn remove_htmx<H: headers_core::Header>(&mut self) -> Option<H> {
+        let values = self.get_all(H::name()).iter().cloned().collect::<Vec<_>>();
+        self.remove(H::name());
+
+        if values.is_empty() {
+            return None;
+        }
+
+        H::decode(&mut values.iter()).ok()
+    }
+}
+
+/// writing any header into a sink of name-value pairs, such as a
+/// `Vec<(HeaderName, HeaderValue)>`.
+///
+/// this is implemented for every [`headers_core::Header`].
+pub trait WriteHeader: headers_core::Header {
+    /// encode this header, extending `sink` with one pair per value.
+    ///
+    /// NOTE: Panics if [`headers_core::Header::encode`] does.
+    fn write_to<E>(&self, sink: &mut E)
+    where
+        E: Extend<(headers_core::HeaderName, headers_core::HeaderValue)>,
+    {
+        let mut values = Vec::new();
+        self.encode(&mut values);
+
+        sink.extend(
+            values
+                .into_iter()
+                .map(|value| (Self::name().clone(), value)),
+        );
+    }
+}
+
+impl<H: headers_core::Header> WriteHeader for H {}
+
+/// an error returned when a header in a [`http::HeaderMap`] is present but
+/// invalid, naming the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidHeader {
+    name: &'static headers_core::HeaderName,
+}
+
+impl InvalidHeader {
+    /// the name of the invalid header.
+    #[must_use]
+    pub const fn name(&self) -> &'static headers_core::HeaderName {
+        self.name
+    }
+
+    /// the status code to reject the request with, `400 Bad Request`.
+    #[must_use]
+    pub const fn status_code(&self) -> http::StatusCode {
+        http::StatusCode::BAD_REQUEST
+    }
+}
+
+impl Display for InvalidHeader {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse {}", self.name)
+    }
+}
+
+impl std::error::Error for InvalidHeader {}
+
+/// decode `H` from `map`, returning `None` if it is absent.
+fn decode_optional<H: headers_core::Header>(
+    map: &http::HeaderMap,
+) -> Result<Option<H>, InvalidHeader> {
+    let mut values = map.get_all(H::name()).iter().peekable();
+
+    if values.peek().is_none() {
+        return Ok(None);
+    }
+
+    H::decode(&mut values)
+        .map(Some)
+        .map_err(|_| InvalidHeader { name: H::name() })
+}
+
+/// encode `header` into `map`, replacing any existing values.
+fn insert_encoded<H: headers_core::Header>(map: &mut http::HeaderMap, header: &H) {
+    let mut values = Vec::new();
+    header.encode(&mut values);
+
+    map.remove(H::name());
+    for value in values {
+        map.append(H::name(), value);
+    }
+}
+
+/// serialize `value` to JSON and use the buffer as a header value without
+/// copying it.
+///
+/// fails if the JSON is not a valid header value, such as when a string
+/// contains a `DEL` character.
+///
+/// NOTE: Panics if the value cannot be serialized.
+#[cfg(feature = "serde")]
+fn json_header_value<T: serde::Serialize + ?Sized>(
+    value: &T,
+) -> Result<headers_core::HeaderValue, http::header::InvalidHeaderValue> {
+    let bytes = bytes::Bytes::from(serde_json::to_vec(value).unwrap());
+    headers_core::HeaderValue::from_maybe_shared(bytes)
+}
+
 macro_rules! define_header {
     {
         $(#[$docs:meta])*
@@ -60,6 +230,29 @@ macro_rules! true_header {
 }
 use true_header;
 
+/// converts the value of a [`convert_header!`] header into a header value.
+trait ToHeaderValue {
+    fn to_header_value(
+        &self,
+    ) -> Result<headers_core::HeaderValue, http::header::InvalidHeaderValue>;
+}
+
+impl ToHeaderValue for http::Uri {
+    fn to_header_value(
+        &self,
+    ) -> Result<headers_core::HeaderValue, http::header::InvalidHeaderValue> {
+        headers_core::HeaderValue::from_str(&self.to_string())
+    }
+}
+
+impl ToHeaderValue for crate::CssSelector {
+    fn to_header_value(
+        &self,
+    ) -> Result<headers_core::HeaderValue, http::header::InvalidHeaderValue> {
+        self.try_into()
+    }
+}
+
 macro_rules! convert_header {
     {
         $(#[$docs:meta])*
@@ -91,9 +284,26 @@ macro_rules! convert_header {
 
             /// NOTE: Panics if the value cannot be converted to a header value.
             fn encode<E: Extend<headers_core::HeaderValue>>(&self, values: &mut E) {
-                let s = self.0.to_string();
-                let header = headers_core::HeaderValue::from_str(&s).unwrap();
+                self.try_encode(values).unwrap();
+            }
+        }
+
+        impl $UpCase {
+            /// encode the header like [`headers_core::Header::encode`], but fail
+            /// instead of panicking if the value is not a valid header value.
+            pub fn try_encode<E: Extend<headers_core::HeaderValue>>(
+                &self,
+                values: &mut E,
+            ) -> Result<(), http::header::InvalidHeaderValue> {
+                let header = crate::headers::ToHeaderValue::to_header_value(&self.0)?;
                 values.extend(std::iter::once(header));
+                Ok(())
+            }
+        }
+
+        impl std::fmt::Display for $UpCase {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                self.0.fmt(f)
             }
         }
     }
@@ -123,7 +333,9 @@ macro_rules! string_header {
             {
                 match (values.next(), values.next()) {
                     (Some(value), None) => {
-                        let s = value.to_str().map_err(|_| headers_core::Error::invalid())?;
+                        // `to_str` rejects non-ascii text, which `encode` accepts.
+                        let s = std::str::from_utf8(value.as_bytes())
+                            .map_err(|_| headers_core::Error::invalid())?;
                         Ok(Self(s.to_owned()))
                     }
                     _ => Err(headers_core::Error::invalid()),
@@ -132,9 +344,675 @@ macro_rules! string_header {
 
             /// NOTE: Panics if the value cannot be converted to a header value.
             fn encode<E: Extend<headers_core::HeaderValue>>(&self, values: &mut E) {
-                values.extend(std::iter::once(headers_core::HeaderValue::from_str(&self.0).unwrap()));
+                self.try_encode(values).unwrap();
+            }
+        }
+
+        impl $UpCase {
+            /// encode the header like [`headers_core::Header::encode`], but fail
+            /// instead of panicking if the value is not a valid header value.
+            pub fn try_encode<E: Extend<headers_core::HeaderValue>>(
+                &self,
+                values: &mut E,
+            ) -> Result<(), http::header::InvalidHeaderValue> {
+                let header = headers_core::HeaderValue::from_str(&self.0)?;
+                values.extend(std::iter::once(header));
+                Ok(())
+            }
+        }
+
+        impl std::fmt::Display for $UpCase {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(&self.0)
             }
         }
     }
 }
 use string_header;
+
+/// every known htmx header, for matching exhaustively over header names.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HtmxHeaderKind {
+    /// [`request::HX_BOOSTED`]
+    Boosted,
+
+    /// [`request::HX_CURRENT_URL`]
+    CurrentUrl,
+
+    /// [`request::HX_HISTORY_RESTORE_REQUEST`]
+    HistoryRestoreRequest,
+
+    /// [`request::HX_PROMPT`]
+    Prompt,
+
+    /// [`request::HX_REQUEST`]
+    Request,
+
+    /// [`request::HX_TARGET`]
+    Target,
+
+    /// [`request::HX_TRIGGER_NAME`]
+    TriggerName,
+
+    /// [`request::HX_TRIGGER`] or [`response::HX_TRIGGER`], which share a name
+    Trigger,
+
+    /// [`response::HX_LOCATION`]
+    Location,
+
+    /// [`response::HX_PUSH_URL`]
+    PushUrl,
+
+    /// [`response::HX_REDIRECT`]
+    Redirect,
+
+    /// [`response::HX_REFRESH`]
+    Refresh,
+
+    /// [`response::HX_REPLACE_URL`]
+    ReplaceUrl,
+
+    /// [`response::HX_RESWAP`]
+    Reswap,
+
+    /// [`response::HX_RETARGET`]
+    Retarget,
+
+    /// [`response::HX_RESELECT`]
+    Reselect,
+
+    /// [`response::HX_TRIGGER_AFTER_SETTLE`]
+    TriggerAfterSettle,
+
+    /// [`response::HX_TRIGGER_AFTER_SWAP`]
+    TriggerAfterSwap,
+}
+
+impl HtmxHeaderKind {
+    /// all header kinds.
+    pub const ALL: [Self; 18] = [
+        Self::Boosted,
+        Self::CurrentUrl,
+        Self::HistoryRestoreRequest,
+        Self::Prompt,
+        Self::Request,
+        Self::Target,
+        Self::TriggerName,
+        Self::Trigger,
+        Self::Location,
+        Self::PushUrl,
+        Self::Redirect,
+        Self::Refresh,
+        Self::ReplaceUrl,
+        Self::Reswap,
+        Self::Retarget,
+        Self::Reselect,
+        Self::TriggerAfterSettle,
+        Self::TriggerAfterSwap,
+    ];
+
+    /// the name of the header.
+    #[must_use]
+    pub fn name(self) -> &'static headers_core::HeaderName {
+        match self {
+            Self::Boosted => &request::HX_BOOSTED,
+            Self::CurrentUrl => &request::HX_CURRENT_URL,
+            Self::HistoryRestoreRequest => &request::HX_HISTORY_RESTORE_REQUEST,
+            Self::Prompt => &request::HX_PROMPT,
+            Self::Request => &request::HX_REQUEST,
+            Self::Target => &request::HX_TARGET,
+            Self::TriggerName => &request::HX_TRIGGER_NAME,
+            Self::Trigger => &request::HX_TRIGGER,
+            Self::Location => &response::HX_LOCATION,
+            Self::PushUrl => &response::HX_PUSH_URL,
+            Self::Redirect => &response::HX_REDIRECT,
+            Self::Refresh => &response::HX_REFRESH,
+            Self::ReplaceUrl => &response::HX_REPLACE_URL,
+            Self::Reswap => &response::HX_RESWAP,
+            Self::Retarget => &response::HX_RETARGET,
+            Self::Reselect => &response::HX_RESELECT,
+            Self::TriggerAfterSettle => &response::HX_TRIGGER_AFTER_SETTLE,
+            Self::TriggerAfterSwap => &response::HX_TRIGGER_AFTER_SWAP,
+        }
+    }
+
+    /// looks up the kind of a header by its name, returning `None` if it is not
+    /// an htmx header.
+    #[must_use]
+    pub fn from_name(name: &headers_core::HeaderName) -> Option<Self> {
+        Self::ALL.into_iter().find(|kind| kind.name() == name)
+    }
+}
+
+/// any htmx header, decoded by [`decode_htmx_header`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodedHtmxHeader {
+    /// a [`request::HxBoosted`] header
+    Boosted(request::HxBoosted),
+
+    /// a [`request::HxCurrentUrl`] header
+    CurrentUrl(request::HxCurrentUrl),
+
+    /// a [`request::HxHistoryRestoreRequest`] header
+    HistoryRestoreRequest(request::HxHistoryRestoreRequest),
+
+    /// a [`request::HxPrompt`] header
+    Prompt(request::HxPrompt),
+
+    /// a [`request::HxRequest`] header
+    Request(request::HxRequest),
+
+    /// a [`request::HxTarget`] header
+    Target(request::HxTarget),
+
+    /// a [`request::HxTriggerName`] header
+    TriggerName(request::HxTriggerName),
+
+    /// a [`response::HxTrigger`] header
+    Trigger(response::HxTrigger),
+
+    /// a [`response::HxLocation`] header, boxed as it is much larger than the
+    /// others
+    #[cfg(feature = "serde")]
+    Location(Box<response::HxLocation>),
+
+    /// a [`response::HxPushUrl`] header
+    PushUrl(response::HxModifyHistory<response::HxPushUrl>),
+
+    /// a [`response::HxRedirect`] header
+    Redirect(response::HxRedirect),
+
+    /// a [`response::HxRefresh`] header
+    Refresh(response::HxRefresh),
+
+    /// a [`response::HxReplaceUrl`] header
+    ReplaceUrl(response::HxModifyHistory<response::HxReplaceUrl>),
+
+    /// a [`response::HxReswap`] header
+    Reswap(response::HxReswap),
+
+    /// a [`response::HxRetarget`] header
+    Retarget(response::HxRetarget),
+
+    /// a [`response::HxReselect`] header
+    Reselect(response::HxReselect),
+
+    /// a [`response::HxTrigger<response::AfterSettle>`] header
+    TriggerAfterSettle(response::HxTrigger<response::AfterSettle>),
+
+    /// a [`response::HxTrigger<response::AfterSwap>`] header
+    TriggerAfterSwap(response::HxTrigger<response::AfterSwap>),
+}
+
+/// decode any htmx header from its name and value, for generic inspection.
+///
+/// returns `None` if `name` is not an htmx header. `hx-trigger` is decoded as
+/// the response [`response::HxTrigger`].
+pub fn decode_htmx_header(
+    name: &headers_core::HeaderName,
+    value: &headers_core::HeaderValue,
+) -> Option<Result<DecodedHtmxHeader, headers_core::Error>> {
+    fn decode<H: headers_core::Header>(
+        value: &headers_core::HeaderValue,
+        variant: impl FnOnce(H) -> DecodedHtmxHeader,
+    ) -> Result<DecodedHtmxHeader, headers_core::Error> {
+        H::decode(&mut std::iter::once(value)).map(variant)
+    }
+
+    Some(match HtmxHeaderKind::from_name(name)? {
+        HtmxHeaderKind::Boosted => decode(value, DecodedHtmxHeader::Boosted),
+        HtmxHeaderKind::CurrentUrl => decode(value, DecodedHtmxHeader::CurrentUrl),
+        HtmxHeaderKind::HistoryRestoreRequest => {
+            decode(value, DecodedHtmxHeader::HistoryRestoreRequest)
+        }
+        HtmxHeaderKind::Prompt => decode(value, DecodedHtmxHeader::Prompt),
+        HtmxHeaderKind::Request => decode(value, DecodedHtmxHeader::Request),
+        HtmxHeaderKind::Target => decode(value, DecodedHtmxHeader::Target),
+        HtmxHeaderKind::TriggerName => decode(value, DecodedHtmxHeader::TriggerName),
+        HtmxHeaderKind::Trigger => decode(value, DecodedHtmxHeader::Trigger),
+        #[cfg(feature = "serde")]
+        HtmxHeaderKind::Location => decode(value, |location| {
+            DecodedHtmxHeader::Location(Box::new(location))
+        }),
+        // `HxLocation` is JSON-backed, so it cannot be decoded without serde.
+        #[cfg(not(feature = "serde"))]
+        HtmxHeaderKind::Location => Err(headers_core::Error::invalid()),
+        HtmxHeaderKind::PushUrl => decode(value, DecodedHtmxHeader::PushUrl),
+        HtmxHeaderKind::Redirect => decode(value, DecodedHtmxHeader::Redirect),
+        HtmxHeaderKind::Refresh => decode(value, DecodedHtmxHeader::Refresh),
+        HtmxHeaderKind::ReplaceUrl => decode(value, DecodedHtmxHeader::ReplaceUrl),
+        HtmxHeaderKind::Reswap => decode(value, DecodedHtmxHeader::Reswap),
+        HtmxHeaderKind::Retarget => decode(value, DecodedHtmxHeader::Retarget),
+        HtmxHeaderKind::Reselect => decode(value, DecodedHtmxHeader::Reselect),
+        HtmxHeaderKind::TriggerAfterSettle => decode(value, DecodedHtmxHeader::TriggerAfterSettle),
+        HtmxHeaderKind::TriggerAfterSwap => decode(value, DecodedHtmxHeader::TriggerAfterSwap),
+    })
+}
+
+/// how [`merge_htmx_headers_with`] combines trigger headers set in both maps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TriggerMerge {
+    /// the overriding triggers replace the base ones, like other headers
+    #[default]
+    Replace,
+
+    /// the overriding triggers are appended after the base ones
+    ///
+    /// the result only decodes if every value is a list of events, as
+    /// written by [`response::HxTrigger::append_to`].
+    Append,
+}
+
+/// merge the htmx headers of `overrides` into `base`, with `overrides`
+/// winning.
+///
+/// each htmx header set in `overrides` replaces all of its values in `base`.
+/// other headers are left alone.
+pub fn merge_htmx_headers(base: &mut http::HeaderMap, overrides: &http::HeaderMap) {
+    merge_htmx_headers_with(base, overrides, TriggerMerge::Replace);
+}
+
+/// merge the htmx headers of `overrides` into `base` like
+/// [`merge_htmx_headers`], combining trigger headers according to `triggers`.
+pub fn merge_htmx_headers_with(
+    base: &mut http::HeaderMap,
+    overrides: &http::HeaderMap,
+    triggers: TriggerMerge,
+) {
+    for kind in HtmxHeaderKind::ALL {
+        let name = kind.name();
+        if !overrides.contains_key(name) {
+            continue;
+        }
+
+        let is_trigger = matches!(
+            kind,
+            HtmxHeaderKind::Trigger
+                | HtmxHeaderKind::TriggerAfterSettle
+                | HtmxHeaderKind::TriggerAfterSwap
+        );
+        if !(is_trigger && triggers == TriggerMerge::Append) {
+            base.remove(name);
+        }
+
+        for value in overrides.get_all(name) {
+            base.append(name, value.clone());
+        }
+    }
+}
+
+/// a combination of htmx response headers with undefined client behavior,
+/// as found by [`lint_htmx_response`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum HtmxLint {
+    /// [`response::HX_REDIRECT`] and [`response::HX_LOCATION`] both redirect
+    RedirectWithLocation,
+
+    /// [`response::HX_REFRESH`] reloads the page, so swap headers are ignored
+    RefreshWithSwap,
+
+    /// [`response::HX_PUSH_URL`] and [`response::HX_REPLACE_URL`] both
+    /// modify the history
+    PushWithReplaceUrl,
+}
+
+impl HtmxLint {
+    /// the conflicting headers.
+    #[must_use]
+    pub const fn headers(self) -> &'static [HtmxHeaderKind] {
+        match self {
+            Self::RedirectWithLocation => &[HtmxHeaderKind::Redirect, HtmxHeaderKind::Location],
+            Self::RefreshWithSwap => &[
+                HtmxHeaderKind::Refresh,
+                HtmxHeaderKind::Reswap,
+                HtmxHeaderKind::Retarget,
+                HtmxHeaderKind::Reselect,
+            ],
+            Self::PushWithReplaceUrl => &[HtmxHeaderKind::PushUrl, HtmxHeaderKind::ReplaceUrl],
+        }
+    }
+}
+
+impl Display for HtmxLint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::RedirectWithLocation => "`hx-redirect` and `hx-location` are both set",
+            Self::RefreshWithSwap => "`hx-refresh` is set alongside swap headers",
+            Self::PushWithReplaceUrl => "`hx-push-url` and `hx-replace-url` are both set",
+        })
+    }
+}
+
+/// check a response's `map` for htmx headers which conflict with each other.
+///
+/// this is advisory: the headers are only checked for presence, not decoded.
+#[must_use]
+pub fn lint_htmx_response(map: &http::HeaderMap) -> Vec<HtmxLint> {
+    let has = |kind: HtmxHeaderKind| map.contains_key(kind.name());
+    let mut lints = Vec::new();
+
+    if has(HtmxHeaderKind::Redirect) && has(HtmxHeaderKind::Location) {
+        lints.push(HtmxLint::RedirectWithLocation);
+    }
+
+    let refresh = map
+        .get(&response::HX_REFRESH)
+        .is_some_and(|value| value == "true");
+    let swap = [
+        HtmxHeaderKind::Reswap,
+        HtmxHeaderKind::Retarget,
+        HtmxHeaderKind::Reselect,
+    ];
+    if refresh && swap.into_iter().any(has) {
+        lints.push(HtmxLint::RefreshWithSwap);
+    }
+
+    if has(HtmxHeaderKind::PushUrl) && has(HtmxHeaderKind::ReplaceUrl) {
+        lints.push(HtmxLint::PushWithReplaceUrl);
+    }
+
+    lints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// assert that `header` decodes back to itself after encoding.
+    #[track_caller]
+    fn assert_round_trips<H>(header: &H)
+    where
+        H: headers_core::Header + PartialEq + std::fmt::Debug,
+    {
+        let mut values = Vec::new();
+        header.encode(&mut values);
+
+        let decoded = H::decode(&mut values.iter());
+        assert_eq!(decoded.as_ref().ok(), Some(header), "encoded as {values:?}");
+    }
+
+    #[test]
+    fn every_header_round_trips() {
+        use http::Uri;
+
+        use crate::{ScrollModifier, ScrollPosition, Swap, SwapStyle};
+
+        assert_round_trips(&request::HxBoosted);
+        assert_round_trips(&request::HxCurrentUrl(Uri::from_static(
+            "https://example.com/page?q=1",
+        )));
+        assert_round_trips(&request::HxHistoryRestoreRequest);
+        assert_round_trips(&request::HxPrompt("yes, delete it".to_owned()));
+        assert_round_trips(&request::HxPrompt("café".to_owned()));
+        assert_round_trips(&request::HxRequest);
+        assert_round_trips(&request::HxTarget("main".to_owned()));
+        assert_round_trips(&request::HxTriggerName("search".to_owned()));
+        assert_round_trips(&request::HxTrigger("item-1,item-2".to_owned()));
+
+        #[cfg(feature = "serde")]
+        assert_round_trips(&response::HxLocation::new(Uri::from_static("/page")));
+        #[cfg(feature = "serde")]
+        assert_round_trips(&response::HxLocation::with_context(
+            Uri::from_static("/page"),
+            response::AjaxContext {
+                target: Some("#main".to_owned()),
+                swap: Some(Swap::OuterHtml.into()),
+                values: Some(std::iter::once(("count".to_owned(), 3.into())).collect()),
+                ..response::AjaxContext::default()
+            },
+        ));
+        assert_round_trips(&response::HxModifyHistory::<response::HxPushUrl>::uri(
+            Uri::from_static("/page"),
+        ));
+        assert_round_trips(&response::HxModifyHistory::<response::HxPushUrl>::no_change());
+        assert_round_trips(&response::HxModifyHistory::<response::HxReplaceUrl>::uri(
+            Uri::from_static("/page"),
+        ));
+        assert_round_trips(&response::HxRedirect(Uri::from_static("/login")));
+        assert_round_trips(&response::HxRefresh);
+        for &swap in Swap::all() {
+            assert_round_trips(&response::HxReswap(swap.into()));
+        }
+        assert_round_trips(&response::HxReswap(SwapStyle {
+            swap: Some(std::time::Duration::from_millis(250)),
+            show: Some(ScrollModifier {
+                selector: Some("#liste-été".into()),
+                position: ScrollPosition::Bottom,
+            }),
+            transition: Some(true),
+            ..SwapStyle::new(Swap::BeforeEnd)
+        }));
+        assert_round_trips(&response::HxRetarget("#other".into()));
+        assert_round_trips(&response::HxReselect("#content".into()));
+        assert_round_trips(&response::HxTrigger::<()>::list([
+            "saved".parse().unwrap(),
+            "notify".parse().unwrap(),
+            "réussi".parse().unwrap(),
+        ]));
+        #[cfg(feature = "serde")]
+        assert_round_trips(
+            &response::HxTrigger::<response::AfterSettle>::builder()
+                .event("first".parse().unwrap())
+                .detail("level", "info")
+                .event("second".parse().unwrap())
+                .detail("count", 2)
+                .build()
+                .unwrap(),
+        );
+        assert_round_trips(&response::HxTrigger::<response::AfterSwap>::list(["done"
+            .parse()
+            .unwrap()]));
+    }
+
+    #[test]
+    fn write_to_extends_name_value_pairs() {
+        let mut sink = Vec::new();
+
+        response::HxRefresh.write_to(&mut sink);
+        response::HxRetarget("#main".into()).write_to(&mut sink);
+        response::HxReswap(crate::Swap::OuterHtml.into()).write_to(&mut sink);
+
+        assert_eq!(
+            sink,
+            [
+                (
+                    response::HX_REFRESH.clone(),
+                    headers_core::HeaderValue::from_static("true")
+                ),
+                (
+                    response::HX_RETARGET.clone(),
+                    headers_core::HeaderValue::from_static("#main")
+                ),
+                (
+                    response::HX_RESWAP.clone(),
+                    headers_core::HeaderValue::from_static("outerHTML")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn typed_get_and_insert() {
+        let mut map = http::HeaderMap::new();
+        assert_eq!(map.typed_get::<response::HxRefresh>(), None);
+
+        map.typed_insert(response::HxRefresh);
+        map.typed_insert(response::HxRetarget("#a".into()));
+        map.typed_insert(response::HxRetarget("#b".into()));
+
+        assert_eq!(map.typed_get(), Some(response::HxRefresh));
+        assert_eq!(map.typed_get(), Some(response::HxRetarget("#b".into())));
+        assert_eq!(map.len(), 2);
+
+        map.insert(
+            &request::HX_CURRENT_URL,
+            headers_core::HeaderValue::from_static("http://[::bad"),
+        );
+        assert_eq!(map.typed_get::<request::HxCurrentUrl>(), None);
+    }
+
+    #[test]
+    fn merge_htmx_headers_overrides_last_wins() {
+        let mut base = http::HeaderMap::new();
+        base.typed_insert(response::HxReswap(crate::Swap::InnerHtml.into()));
+        base.typed_insert(response::HxRetarget("#main".into()));
+        base.typed_insert(response::HxTrigger::<()>::list(["a".parse().unwrap()]));
+        base.insert(http::header::CACHE_CONTROL, "no-store".parse().unwrap());
+
+        let mut overrides = http::HeaderMap::new();
+        overrides.typed_insert(response::HxReswap(crate::Swap::OuterHtml.into()));
+        overrides.typed_insert(response::HxTrigger::<()>::list(["b".parse().unwrap()]));
+
+        let mut merged = base.clone();
+        merge_htmx_headers(&mut merged, &overrides);
+        assert_eq!(
+            merged.typed_get(),
+            Some(response::HxReswap(crate::Swap::OuterHtml.into()))
+        );
+        assert_eq!(merged.get_all(&response::HX_RESWAP).iter().count(), 1);
+        assert_eq!(merged[&response::HX_RETARGET], "#main");
+        assert_eq!(merged[http::header::CACHE_CONTROL], "no-store");
+        assert_eq!(
+            merged.typed_get(),
+            Some(response::HxTrigger::<()>::list(["b".parse().unwrap()]))
+        );
+
+        merge_htmx_headers_with(&mut base, &overrides, TriggerMerge::Append);
+        assert_eq!(
+            base.typed_get(),
+            Some(response::HxTrigger::<()>::list([
+                "a".parse().unwrap(),
+                "b".parse().unwrap()
+            ]))
+        );
+    }
+
+    #[test]
+    fn remove_htmx_decodes_removed_value() {
+        let mut map = http::HeaderMap::new();
+        insert_encoded(&mut map, &response::HxReswap(crate::Swap::BeforeEnd.into()));
+
+        assert_eq!(
+            map.remove_htmx::<response::HxReswap>(),
+            Some(response::HxReswap(crate::Swap::BeforeEnd.into()))
+        );
+        assert!(map.is_empty());
+        assert_eq!(map.remove_htmx::<response::HxReswap>(), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn json_header_value_matches_from_str() {
+        let value = serde_json::json!({ "event": { "level": "info", "ids": [1, 2, 3] } });
+
+        let shared = json_header_value(&value).unwrap();
+        let copied = headers_core::HeaderValue::from_str(&value.to_string()).unwrap();
+
+        assert_eq!(shared, copied);
+        assert_eq!(shared.to_str().unwrap(), copied.to_str().unwrap());
+    }
+
+    #[test]
+    fn header_kind_from_name() {
+        let cases = [
+            ("hx-boosted", HtmxHeaderKind::Boosted),
+            ("hx-current-url", HtmxHeaderKind::CurrentUrl),
+            (
+                "hx-history-restore-request",
+                HtmxHeaderKind::HistoryRestoreRequest,
+            ),
+            ("hx-prompt", HtmxHeaderKind::Prompt),
+            ("hx-request", HtmxHeaderKind::Request),
+            ("hx-target", HtmxHeaderKind::Target),
+            ("hx-trigger-name", HtmxHeaderKind::TriggerName),
+            ("hx-trigger", HtmxHeaderKind::Trigger),
+            ("hx-location", HtmxHeaderKind::Location),
+            ("hx-push-url", HtmxHeaderKind::PushUrl),
+            ("hx-redirect", HtmxHeaderKind::Redirect),
+            ("hx-refresh", HtmxHeaderKind::Refresh),
+            ("hx-replace-url", HtmxHeaderKind::ReplaceUrl),
+            ("hx-reswap", HtmxHeaderKind::Reswap),
+            ("hx-retarget", HtmxHeaderKind::Retarget),
+            ("hx-reselect", HtmxHeaderKind::Reselect),
+            (
+                "hx-trigger-after-settle",
+                HtmxHeaderKind::TriggerAfterSettle,
+            ),
+            ("hx-trigger-after-swap", HtmxHeaderKind::TriggerAfterSwap),
+        ];
+
+        for (name, kind) in cases {
+            let name = headers_core::HeaderName::from_static(name);
+            assert_eq!(HtmxHeaderKind::from_name(&name), Some(kind));
+        }
+
+        let unknown = headers_core::HeaderName::from_static("x-unknown");
+        assert_eq!(HtmxHeaderKind::from_name(&unknown), None);
+    }
+
+    #[test]
+    fn decode_htmx_header_dispatches_on_name() {
+        let decoded = decode_htmx_header(
+            &response::HX_RESWAP,
+            &headers_core::HeaderValue::from_static("outerHTML"),
+        );
+        claims::assert_ok_eq!(
+            decoded.unwrap(),
+            DecodedHtmxHeader::Reswap(response::HxReswap(crate::Swap::OuterHtml.into()))
+        );
+
+        let decoded = decode_htmx_header(
+            &request::HX_CURRENT_URL,
+            &headers_core::HeaderValue::from_static("/page"),
+        );
+        claims::assert_ok_eq!(
+            decoded.unwrap(),
+            DecodedHtmxHeader::CurrentUrl(request::HxCurrentUrl(http::Uri::from_static("/page")))
+        );
+
+        let decoded = decode_htmx_header(
+            &request::HX_REQUEST,
+            &headers_core::HeaderValue::from_static("false"),
+        );
+        claims::assert_err!(decoded.unwrap());
+
+        let decoded = decode_htmx_header(
+            &headers_core::HeaderName::from_static("x-unknown"),
+            &headers_core::HeaderValue::from_static("true"),
+        );
+        assert!(decoded.is_none());
+    }
+
+    #[test]
+    fn lint_htmx_response_finds_conflicts() {
+        let mut map = http::HeaderMap::new();
+        assert_eq!(lint_htmx_response(&map), []);
+
+        map.insert(
+            &response::HX_REDIRECT,
+            http::HeaderValue::from_static("/login"),
+        );
+        map.insert(
+            &response::HX_LOCATION,
+            http::HeaderValue::from_static("/home"),
+        );
+        assert_eq!(lint_htmx_response(&map), [HtmxLint::RedirectWithLocation]);
+
+        let mut map = http::HeaderMap::new();
+        map.insert(
+            &response::HX_REFRESH,
+            http::HeaderValue::from_static("true"),
+        );
+        assert_eq!(lint_htmx_response(&map), []);
+
+        map.insert(
+            &response::HX_RESWAP,
+            http::HeaderValue::from_static("outerHTML"),
+        );
+        assert_eq!(lint_htmx_response(&map), [HtmxLint::RefreshWithSwap]);
+        assert!(HtmxLint::RefreshWithSwap
+            .headers()
+            .contains(&HtmxHeaderKind::Reswap));
+    }
+}
@@ -1,8 +1,12 @@
 //! htmx request headers.
 
-use http::Uri;
+use std::fmt::{self, Display, Formatter};
 
-use super::{convert_header, define_header, string_header, true_header};
+use http::{request::Parts, uri::PathAndQuery, HeaderMap, Request, Uri};
+
+use super::{
+    convert_header, decode_optional, define_header, string_header, true_header, InvalidHeader,
+};
 
 true_header! {
     /// indicates that the request is via an element using [hx-boost](https://htmx.org/attributes/hx-boost/)
@@ -14,6 +18,78 @@ convert_header! {
     Uri => (HX_CURRENT_URL, HxCurrentUrl, "hx-current-url")
 }
 
+impl HxCurrentUrl {
+    /// whether the url's path is `prefix` or under it, comparing whole
+    /// segments.
+    ///
+    /// trailing slashes are ignored, so `/admin/` matches `/admin` and
+    /// `/admin/users`, but not `/administrator`.
+    #[must_use]
+    pub fn starts_with_path(&self, prefix: &str) -> bool {
+        let mut segments = path_segments(self.0.path());
+        path_segments(prefix).all(|prefix| segments.next() == Some(prefix))
+    }
+
+    /// whether the url's path matches `pattern`, where segments starting with
+    /// `:` match any single segment.
+    ///
+    /// trailing slashes are ignored, so `/users/:id` matches `/users/42/`.
+    #[must_use]
+    pub fn path_matches(&self, pattern: &str) -> bool {
+        let mut segments = path_segments(self.0.path());
+        let mut pattern = path_segments(pattern);
+
+        loop {
+            match (segments.next(), pattern.next()) {
+                (None, None) => return true,
+                (Some(segment), Some(expected))
+                    if expected.starts_with(':') || segment == expected => {}
+                _ => return false,
+            }
+        }
+    }
+
+    /// whether the url is path-only, without a scheme or host.
+    #[must_use]
+    pub fn is_relative(&self) -> bool {
+        self.0.authority().is_none()
+    }
+
+    /// resolve a path-only url against the scheme and host of `origin`.
+    ///
+    /// absolute urls, and urls which cannot be resolved because `origin` has
+    /// no scheme or host, are returned unchanged.
+    #[must_use]
+    pub fn resolve_origin(&self, origin: &Uri) -> Uri {
+        if !self.is_relative() {
+            return self.0.clone();
+        }
+
+        let mut parts = origin.clone().into_parts();
+        parts.path_and_query = Some(
+            self.0
+                .path_and_query()
+                .cloned()
+                .unwrap_or_else(|| PathAndQuery::from_static("/")),
+        );
+
+        Uri::from_parts(parts).unwrap_or_else(|_| self.0.clone())
+    }
+}
+
+/// whether `headers` is from an htmx request, with `HX-Request: true`.
+///
+/// like [`HxRequest`]'s decoding, any other value is treated as absent.
+#[must_use]
+pub fn is_htmx(headers: &HeaderMap) -> bool {
+    matches!(decode_optional::<HxRequest>(headers), Ok(Some(HxRequest)))
+}
+
+/// the non-empty segments of `path`.
+fn path_segments(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/').filter(|segment| !segment.is_empty())
+}
+
 true_header! {
     /// “true” if the request is for history restoration after a miss in the local history cache
     (HX_HISTORY_RESTORE_REQUEST, HxHistoryRestoreRequest, "hx-history-restore-request")
@@ -41,5 +117,390 @@ string_header! {
 
 string_header! {
     /// the `id` of the triggered element if it exists
+    ///
+    /// unlike the response [`HxTrigger`](super::response::HxTrigger), the value
+    /// is a single opaque id and is never split on commas.
     (HX_TRIGGER, HxTrigger, "hx-trigger")
 }
+
+/// all htmx request headers, decoded at once.
+///
+/// each field is `None` if its header is absent.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct RequestHeaders {
+    /// the [`HxBoosted`] header
+    pub boosted: Option<HxBoosted>,
+
+    /// the [`HxCurrentUrl`] header
+    pub current_url: Option<HxCurrentUrl>,
+
+    /// the [`HxHistoryRestoreRequest`] header
+    pub history_restore_request: Option<HxHistoryRestoreRequest>,
+
+    /// the [`HxPrompt`] header
+    pub prompt: Option<HxPrompt>,
+
+    /// the [`HxRequest`] header
+    pub request: Option<HxRequest>,
+
+    /// the [`HxTarget`] header
+    pub target: Option<HxTarget>,
+
+    /// the [`HxTriggerName`] header
+    pub trigger_name: Option<HxTriggerName>,
+
+    /// the [`HxTrigger`] header
+    pub trigger: Option<HxTrigger>,
+}
+
+impl RequestHeaders {
+    /// create an empty set of request headers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// summarize the element the user interacted with, from the [`HxTrigger`],
+    /// [`HxTriggerName`], and [`HxTarget`] headers.
+    #[must_use]
+    pub fn user_triggered_event(&self) -> UserInteraction {
+        UserInteraction {
+            trigger_id: self.trigger.as_ref().map(|HxTrigger(id)| id.clone()),
+            trigger_name: self
+                .trigger_name
+                .as_ref()
+                .map(|HxTriggerName(name)| name.clone()),
+            target_id: self.target.as_ref().map(|HxTarget(id)| id.clone()),
+        }
+    }
+
+    /// decode all htmx request headers from `map`.
+    ///
+    /// fails if any header is present but invalid.
+    pub fn from_header_map(map: &HeaderMap) -> Result<Self, InvalidHeader> {
+        Ok(Self {
+            boosted: decode_optional(map)?,
+            current_url: decode_optional(map)?,
+            history_restore_request: decode_optional(map)?,
+            prompt: decode_optional(map)?,
+            request: decode_optional(map)?,
+            target: decode_optional(map)?,
+            trigger_name: decode_optional(map)?,
+            trigger: decode_optional(map)?,
+        })
+    }
+
+    /// decode all htmx request headers from the request `parts`.
+    ///
+    /// fails if any header is present but invalid.
+    pub fn from_parts(parts: &Parts) -> Result<Self, InvalidHeader> {
+        Self::from_header_map(&parts.headers)
+    }
+}
+
+impl TryFrom<&HeaderMap> for RequestHeaders {
+    type Error = InvalidHeader;
+
+    fn try_from(map: &HeaderMap) -> Result<Self, Self::Error> {
+        Self::from_header_map(map)
+    }
+}
+
+impl TryFrom<&Parts> for RequestHeaders {
+    type Error = InvalidHeader;
+
+    fn try_from(parts: &Parts) -> Result<Self, Self::Error> {
+        Self::from_parts(parts)
+    }
+}
+
+impl<B> TryFrom<&Request<B>> for RequestHeaders {
+    type Error = InvalidHeader;
+
+    fn try_from(request: &Request<B>) -> Result<Self, Self::Error> {
+        Self::from_header_map(request.headers())
+    }
+}
+
+/// the element a user interacted with, as returned by
+/// [`RequestHeaders::user_triggered_event`].
+///
+/// each field is `None` if its header is absent.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct UserInteraction {
+    /// the `id` of the triggered element
+    pub trigger_id: Option<String>,
+
+    /// the `name` of the triggered element
+    pub trigger_name: Option<String>,
+
+    /// the `id` of the target element
+    pub target_id: Option<String>,
+}
+
+impl Display for UserInteraction {
+    /// summarizes the interaction for logs, such as
+    /// `#save (name=save) -> #main`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match (&self.trigger_id, &self.trigger_name) {
+            (Some(id), Some(name)) => write!(f, "#{id} (name={name})")?,
+            (Some(id), None) => write!(f, "#{id}")?,
+            (None, Some(name)) => write!(f, "(name={name})")?,
+            (None, None) => f.write_str("unknown element")?,
+        }
+
+        if let Some(target) = &self.target_id {
+            write!(f, " -> #{target}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// what kind of response a request wants, as classified by
+/// [`ResponseKind::from_request`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResponseKind {
+    /// a full page, for a non-htmx request or a history restore request
+    FullPage,
+
+    /// a page body for a boosted link or form
+    BoostedFragment,
+
+    /// a fragment to swap into the target element
+    TargetedFragment {
+        /// the `id` of the target element, if it has one
+        target: Option<String>,
+    },
+}
+
+impl ResponseKind {
+    /// classify a request by its htmx headers.
+    ///
+    /// - requests without [`HxRequest`], or with [`HxHistoryRestoreRequest`]
+    ///   (which htmx sends after a history cache miss), want a
+    ///   [`ResponseKind::FullPage`].
+    /// - otherwise, requests with [`HxBoosted`] want a
+    ///   [`ResponseKind::BoostedFragment`].
+    /// - all other htmx requests want a [`ResponseKind::TargetedFragment`],
+    ///   with the [`HxTarget`] if present.
+    #[must_use]
+    pub fn from_request(headers: &RequestHeaders) -> Self {
+        match headers {
+            RequestHeaders { request: None, .. }
+            | RequestHeaders {
+                history_restore_request: Some(_),
+                ..
+            } => Self::FullPage,
+            RequestHeaders {
+                boosted: Some(_), ..
+            } => Self::BoostedFragment,
+            RequestHeaders { target, .. } => Self::TargetedFragment {
+                target: target.as_ref().map(|HxTarget(target)| target.clone()),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_headers_from_request() {
+        let request = Request::builder()
+            .header("hx-request", "true")
+            .header("hx-current-url", "https://example.com/page")
+            .header("hx-target", "main")
+            .body(())
+            .unwrap();
+
+        let expected = RequestHeaders {
+            boosted: None,
+            current_url: Some(HxCurrentUrl(Uri::from_static("https://example.com/page"))),
+            history_restore_request: None,
+            prompt: None,
+            request: Some(HxRequest),
+            target: Some(HxTarget("main".to_owned())),
+            trigger_name: None,
+            trigger: None,
+        };
+
+        claims::assert_ok_eq!(RequestHeaders::try_from(&request), expected);
+
+        let (parts, ()) = request.into_parts();
+        claims::assert_ok_eq!(RequestHeaders::try_from(&parts), expected);
+    }
+
+    #[test]
+    fn request_headers_from_parts() {
+        fn extract(parts: &Parts) -> Result<Option<HxTarget>, Box<dyn std::error::Error>> {
+            let headers = RequestHeaders::from_parts(parts)?;
+            Ok(headers.target)
+        }
+
+        let (parts, ()) = Request::builder()
+            .header("hx-target", "main")
+            .body(())
+            .unwrap()
+            .into_parts();
+        claims::assert_some_eq!(extract(&parts).unwrap(), HxTarget("main".to_owned()));
+
+        let (parts, ()) = Request::builder()
+            .header("hx-current-url", "http://[::bad")
+            .body(())
+            .unwrap()
+            .into_parts();
+        let err = claims::assert_err!(RequestHeaders::from_parts(&parts));
+        assert_eq!(err.name(), &HX_CURRENT_URL);
+        assert_eq!(err.status_code(), http::StatusCode::BAD_REQUEST);
+        claims::assert_err!(extract(&parts));
+    }
+
+    #[test]
+    fn is_htmx_requires_exact_true() {
+        let mut map = HeaderMap::new();
+        assert!(!is_htmx(&map));
+
+        map.insert(&HX_REQUEST, http::HeaderValue::from_static("true"));
+        assert!(is_htmx(&map));
+
+        map.insert(&HX_REQUEST, http::HeaderValue::from_static("TRUE"));
+        assert!(!is_htmx(&map));
+
+        map.insert(&HX_REQUEST, http::HeaderValue::from_static("false"));
+        assert!(!is_htmx(&map));
+    }
+
+    #[test]
+    fn trigger_is_not_comma_split() {
+        let val = http::HeaderValue::from_static("item-1,item-2");
+
+        claims::assert_ok_eq!(
+            <HxTrigger as headers_core::Header>::decode(&mut std::iter::once(&val)),
+            HxTrigger("item-1,item-2".to_owned())
+        );
+    }
+
+    #[test]
+    fn request_headers_name_invalid_header() {
+        let mut map = HeaderMap::new();
+        map.insert(
+            &HX_CURRENT_URL,
+            http::HeaderValue::from_static("http://[::bad"),
+        );
+
+        let err = claims::assert_err!(RequestHeaders::from_header_map(&map));
+        assert_eq!(err.name(), &HX_CURRENT_URL);
+        assert_eq!(err.to_string(), "failed to parse hx-current-url");
+    }
+
+    #[test]
+    fn display_inner_value() {
+        assert_eq!(HxTarget("main".to_owned()).to_string(), "main");
+        assert_eq!(HxPrompt("yes".to_owned()).to_string(), "yes");
+        assert_eq!(
+            HxCurrentUrl(Uri::from_static("https://example.com/page")).to_string(),
+            "https://example.com/page"
+        );
+    }
+
+    #[test]
+    fn current_url_path_helpers() {
+        let url = HxCurrentUrl(Uri::from_static("https://example.com/admin/users/42/"));
+
+        assert!(url.starts_with_path("/admin"));
+        assert!(url.starts_with_path("/admin/"));
+        assert!(url.starts_with_path("/"));
+        assert!(!url.starts_with_path("/adm"));
+        assert!(!url.starts_with_path("/admin/users/42/edit"));
+
+        assert!(url.path_matches("/admin/users/:id"));
+        assert!(url.path_matches("/admin/:section/:id/"));
+        assert!(!url.path_matches("/admin/users"));
+        assert!(!url.path_matches("/admin/groups/:id"));
+    }
+
+    #[test]
+    fn current_url_resolves_relative() {
+        let origin = Uri::from_static("https://example.com");
+
+        let relative = HxCurrentUrl(Uri::from_static("/page?tab=2"));
+        assert!(relative.is_relative());
+        assert_eq!(
+            relative.resolve_origin(&origin),
+            Uri::from_static("https://example.com/page?tab=2")
+        );
+
+        let absolute = HxCurrentUrl(Uri::from_static("http://other.test/page"));
+        assert!(!absolute.is_relative());
+        assert_eq!(
+            absolute.resolve_origin(&origin),
+            Uri::from_static("http://other.test/page")
+        );
+    }
+
+    #[test]
+    fn user_triggered_event_with_partial_data() {
+        let headers = RequestHeaders {
+            trigger_name: Some(HxTriggerName("save".to_owned())),
+            target: Some(HxTarget("main".to_owned())),
+            ..RequestHeaders::new()
+        };
+
+        let interaction = headers.user_triggered_event();
+        assert_eq!(
+            interaction,
+            UserInteraction {
+                trigger_id: None,
+                trigger_name: Some("save".to_owned()),
+                target_id: Some("main".to_owned()),
+            }
+        );
+        assert_eq!(interaction.to_string(), "(name=save) -> #main");
+
+        let headers = RequestHeaders {
+            trigger: Some(HxTrigger("save-btn".to_owned())),
+            ..headers
+        };
+        assert_eq!(
+            headers.user_triggered_event().to_string(),
+            "#save-btn (name=save) -> #main"
+        );
+        assert_eq!(
+            RequestHeaders::new().user_triggered_event().to_string(),
+            "unknown element"
+        );
+    }
+
+    #[test]
+    fn response_kind_classifies_requests() {
+        assert_eq!(
+            ResponseKind::from_request(&RequestHeaders::new()),
+            ResponseKind::FullPage
+        );
+
+        let boosted = RequestHeaders {
+            request: Some(HxRequest),
+            boosted: Some(HxBoosted),
+            target: Some(HxTarget("main".to_owned())),
+            ..RequestHeaders::new()
+        };
+        assert_eq!(
+            ResponseKind::from_request(&boosted),
+            ResponseKind::BoostedFragment
+        );
+
+        let targeted = RequestHeaders {
+            request: Some(HxRequest),
+            target: Some(HxTarget("main".to_owned())),
+            ..RequestHeaders::new()
+        };
+        assert_eq!(
+            ResponseKind::from_request(&targeted),
+            ResponseKind::TargetedFragment {
+                target: Some("main".to_owned())
+            }
+        );
+    }
+}
@@ -1,15 +1,98 @@
 //! htmx response headers.
 
-use std::collections::HashMap;
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    str::FromStr,
+};
 
 use headers_core::{Header, HeaderValue};
 use http::{HeaderName, Uri};
-use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
-use super::{convert_header, define_header, string_header, true_header};
-use crate::Swap;
+use super::{
+    convert_header, decode_optional, define_header, insert_encoded, true_header, DecodeError,
+    InvalidHeader,
+};
+#[cfg(feature = "serde")]
+use super::{
+    json_header_value,
+    request::{self, HxPrompt, RequestHeaders},
+};
+use crate::{CssSelector, ParseSwapStyleError, Swap, SwapStyle};
+
+/// serializes key-value pairs as a map, keeping their order.
+#[cfg(feature = "serde")]
+struct MapRef<'a, K, V>(&'a [(K, V)]);
+
+#[cfg(feature = "serde")]
+impl<K: Serialize, V: Serialize> Serialize for MapRef<'_, K, V> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+    }
+}
+
+/// deserializes key-value pairs from a map, keeping their order.
+#[cfg(feature = "serde")]
+struct OrderedMap<K, V>(Vec<(K, V)>);
+
+#[cfg(feature = "serde")]
+impl<'de, K: Deserialize<'de>, V: Deserialize<'de>> Deserialize<'de> for OrderedMap<K, V> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor<K, V>(PhantomData<(K, V)>);
+
+        impl<'de, K: Deserialize<'de>, V: Deserialize<'de>> serde::de::Visitor<'de> for Visitor<K, V> {
+            type Value = OrderedMap<K, V>;
+
+            fn expecting(&self, f: &mut Formatter<'_>) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+
+                Ok(OrderedMap(entries))
+            }
+        }
+
+        deserializer.deserialize_map(Visitor(PhantomData))
+    }
+}
+
+/// `serde(with)` functions for an optional, ordered map.
+#[cfg(feature = "serde")]
+mod optional_ordered_map {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::{MapRef, OrderedMap};
+
+    // the signature is fixed by `serde(with)`.
+    #[allow(clippy::ref_option)]
+    pub fn serialize<S: Serializer, V: Serialize>(
+        map: &Option<Vec<(String, V)>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        map.as_deref().map(MapRef).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>, V: Deserialize<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Vec<(String, V)>>, D::Error> {
+        Ok(Option::<OrderedMap<String, V>>::deserialize(deserializer)?.map(|OrderedMap(map)| map))
+    }
+}
 
 /// ajax context for use with [`HxLocation`].
+#[cfg(feature = "serde")]
 #[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
 pub struct AjaxContext {
     /// the source element of the request
@@ -30,40 +113,159 @@ pub struct AjaxContext {
 
     /// how the response will be swapped in relative to the target
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub swap: Option<String>,
+    pub swap: Option<SwapStyle>,
 
-    /// values to submit with the request
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub values: Option<HashMap<String, String>>,
+    /// values to submit with the request, in order
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "optional_ordered_map"
+    )]
+    pub values: Option<Vec<(String, serde_json::Value)>>,
 
-    /// headers to submit with the request
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub headers: Option<HashMap<String, String>>,
+    /// headers to submit with the request, in order
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "optional_ordered_map"
+    )]
+    pub headers: Option<Vec<(String, String)>>,
 
     /// allows you to select the content you want swapped from a response
     #[serde(skip_serializing_if = "Option::is_none")]
     pub select: Option<String>,
 }
 
-define_header! {
-    /// allows you to do a client-side redirect that does not do a full page reload
+#[cfg(feature = "serde")]
+impl AjaxContext {
+    /// set [`values`](Self::values) from the fields of `values`, such as a
+    /// struct of form values.
+    ///
+    /// fields are kept in the order they are serialized. fails if `values`
+    /// does not serialize to an object.
+    pub fn set_values<T: Serialize>(&mut self, values: &T) -> serde_json::Result<()> {
+        let OrderedMap(values) = serde_json::from_slice(&serde_json::to_vec(values)?)?;
+        self.values = Some(values);
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl FromStr for AjaxContext {
+    type Err = serde_json::Error;
+
+    /// parses the JSON object form of the context, such as
+    /// `{"target":"#main"}`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<&str> for AjaxContext {
+    type Error = serde_json::Error;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// allows you to do a client-side redirect that does not do a full page reload
+///
+/// [htmx docs](https://htmx.org/headers/hx-location/)
+pub static HX_LOCATION: HeaderName = HeaderName::from_static("hx-location");
+
+/// allows you to do a client-side redirect that does not do a full page reload
+///
+/// an all-default `context` compares equal to `None`.
+///
+/// [htmx docs](https://htmx.org/headers/hx-location/)
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HxLocation {
+    /// url to load the response from.
+    #[serde(with = "http_serde::uri")]
+    pub path: Uri,
+
+    /// other data, which mirrors the [ajax](https://htmx.org/api/#ajax) api context.
+    #[serde(flatten)]
+    pub context: Option<AjaxContext>,
+}
+
+#[cfg(feature = "serde")]
+impl HxLocation {
+    /// a location which just redirects to `path`, without any context.
+    pub fn new(path: impl Into<Uri>) -> Self {
+        Self {
+            path: path.into(),
+            context: None,
+        }
+    }
+
+    /// a location which redirects to `path` with `context`.
+    ///
+    /// an all-default `context` is normalized to `None`.
+    pub fn with_context(path: impl Into<Uri>, context: AjaxContext) -> Self {
+        Self {
+            path: path.into(),
+            context: (context != AjaxContext::default()).then_some(context),
+        }
+    }
+
+    /// a location which redirects to `path`, keeping the ajax context of the
+    /// incoming request.
     ///
-    /// [htmx docs](https://htmx.org/headers/hx-location/)
-    (HX_LOCATION, "hx-location")
+    /// the `target` and `source` are the request's
+    /// [`HxTarget`](request::HxTarget)
+    /// and [`HxTrigger`](request::HxTrigger) ids, as `#id` selectors.
+    pub fn from_request(path: impl Into<Uri>, headers: &RequestHeaders) -> Self {
+        let context = AjaxContext {
+            target: headers
+                .target
+                .as_ref()
+                .map(|request::HxTarget(id)| format!("#{id}")),
+            source: headers
+                .trigger
+                .as_ref()
+                .map(|request::HxTrigger(id)| format!("#{id}")),
+            ..AjaxContext::default()
+        };
+
+        Self::with_context(path, context)
+    }
+
+    /// the context, or `None` if it is all-default.
+    fn normalized_context(&self) -> Option<&AjaxContext> {
+        self.context
+            .as_ref()
+            .filter(|context| **context != AjaxContext::default())
+    }
+}
 
+#[cfg(feature = "serde")]
+impl PartialEq for HxLocation {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.normalized_context() == other.normalized_context()
+    }
+}
 
-    #[derive(Serialize, Deserialize)]
-    pub struct HxLocation {
-        /// url to load the response from.
-        #[serde(with = "http_serde::uri")]
-        pub path: Uri,
+#[cfg(feature = "serde")]
+impl Eq for HxLocation {}
 
-        /// other data, which mirrors the [ajax](https://htmx.org/api/#ajax) api context.
-        #[serde(flatten)]
-        pub context: Option<AjaxContext>,
+#[cfg(feature = "serde")]
+impl Display for HxLocation {
+    /// formats the header value, as [`Header::encode`] would encode it.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.normalized_context().is_none() {
+            self.path.fmt(f)
+        } else {
+            f.write_str(&serde_json::to_string(self).map_err(|_| fmt::Error)?)
+        }
     }
 }
 
+#[cfg(feature = "serde")]
 impl Header for HxLocation {
     fn name() -> &'static HeaderName {
         &HX_LOCATION
@@ -75,43 +277,148 @@ impl Header for HxLocation {
         I: Iterator<Item = &'i HeaderValue>,
     {
         match (values.next(), values.next()) {
-            (Some(value), None) => {
-                serde_json::from_slice(value.as_bytes()).map_err(|_| headers_core::Error::invalid())
-            }
+            (Some(value), None) => Ok(value.try_into()?),
             _ => Err(headers_core::Error::invalid()),
         }
     }
 
     /// NOTE: Panics if the value cannot be converted to a header value.
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
-        let header = match self {
-            Self {
-                path,
-                context: None,
-            } => HeaderValue::from_str(&path.to_string()).unwrap(),
-            Self {
-                context: Some(_), ..
-            } => {
-                let s = serde_json::to_string(self).unwrap();
-                HeaderValue::from_str(&s).unwrap()
-            }
+        self.try_encode(values).unwrap();
+    }
+}
+
+#[cfg(feature = "serde")]
+impl HxLocation {
+    /// encode the header like [`Header::encode`], but fail instead of
+    /// panicking if the context is not a valid header value.
+    ///
+    /// a location without context always encodes.
+    pub fn try_encode<E: Extend<HeaderValue>>(
+        &self,
+        values: &mut E,
+    ) -> Result<(), http::header::InvalidHeaderValue> {
+        let header = if self.normalized_context().is_none() {
+            HeaderValue::from_str(&self.path.to_string())?
+        } else {
+            json_header_value(self)?
         };
 
         values.extend(std::iter::once(header));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Uri> for HxLocation {
+    fn from(path: Uri) -> Self {
+        Self::new(path)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<(Uri, AjaxContext)> for HxLocation {
+    /// an all-default `context` is normalized to `None`.
+    fn from((path, context): (Uri, AjaxContext)) -> Self {
+        Self::with_context(path, context)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<&HeaderValue> for HxLocation {
+    type Error = DecodeError;
+
+    /// decodes either the JSON object form or a bare path.
+    fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
+        let bytes = value.as_bytes();
+
+        serde_json::from_slice(bytes).or_else(|_| {
+            Uri::try_from(bytes)
+                .map(|path| Self {
+                    path,
+                    context: None,
+                })
+                .map_err(|_| DecodeError::new("not valid JSON and not a valid URI"))
+        })
     }
 }
 
 /// to be used with [`HxPushUrl`] or [`HxReplaceUrl`].
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum HxModifyHistory<M: HistoryModification> {
+#[derive(Debug, Clone)]
+pub struct HxModifyHistory<M: HistoryModification> {
+    value: HistoryValue,
+    _marker: PhantomData<M>,
+}
+
+impl<M: HistoryModification> HxModifyHistory<M> {
+    /// modify the history with `uri`.
+    #[must_use]
+    pub const fn uri(uri: Uri) -> Self {
+        Self::new(HistoryValue::Uri(uri))
+    }
+
+    /// do not change the history.
+    #[must_use]
+    pub const fn no_change() -> Self {
+        Self::new(HistoryValue::NoChange)
+    }
+
+    /// create the header from a [`HistoryValue`].
+    #[must_use]
+    pub const fn new(value: HistoryValue) -> Self {
+        Self {
+            value,
+            _marker: PhantomData,
+        }
+    }
+
+    /// the value of the header.
+    #[must_use]
+    pub const fn value(&self) -> &HistoryValue {
+        &self.value
+    }
+
+    /// take the value of the header.
+    #[must_use]
+    pub fn into_value(self) -> HistoryValue {
+        self.value
+    }
+}
+
+impl<M: HistoryModification> From<HistoryValue> for HxModifyHistory<M> {
+    fn from(value: HistoryValue) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<M: HistoryModification> From<Uri> for HxModifyHistory<M> {
+    fn from(uri: Uri) -> Self {
+        Self::uri(uri)
+    }
+}
+
+impl<M: HistoryModification> PartialEq for HxModifyHistory<M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.value == other.value
+    }
+}
+
+impl<M: HistoryModification> Eq for HxModifyHistory<M> {}
+
+impl<M: HistoryModification> Hash for HxModifyHistory<M> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value.hash(state);
+    }
+}
+
+/// the value of an [`HxModifyHistory`] header.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum HistoryValue {
     /// a url to modify the history with.
     Uri(Uri),
 
     /// do not change the history.
     NoChange,
-    #[doc(hidden)]
-    #[allow(dead_code)]
-    Phantom(std::marker::PhantomData<M>),
 }
 
 /// history modification headers.
@@ -152,6 +459,31 @@ impl HistoryModification for HxReplaceUrl {
     }
 }
 
+impl<M: HistoryModification> TryFrom<&HeaderValue> for HxModifyHistory<M> {
+    type Error = DecodeError;
+
+    /// decodes either `false` or a url.
+    fn try_from(value: &HeaderValue) -> Result<Self, Self::Error> {
+        if value == "false" {
+            Ok(Self::no_change())
+        } else {
+            Uri::try_from(value.as_bytes())
+                .map(Self::uri)
+                .map_err(|_| DecodeError::new("not `false` and not a valid URI"))
+        }
+    }
+}
+
+impl<M: HistoryModification> Display for HxModifyHistory<M> {
+    /// formats the header value, as [`Header::encode`] would encode it.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.value {
+            HistoryValue::Uri(uri) => uri.fmt(f),
+            HistoryValue::NoChange => f.write_str("false"),
+        }
+    }
+}
+
 impl<M: HistoryModification> Header for HxModifyHistory<M> {
     fn name() -> &'static HeaderName {
         M::name()
@@ -163,27 +495,16 @@ impl<M: HistoryModification> Header for HxModifyHistory<M> {
         I: Iterator<Item = &'i HeaderValue>,
     {
         match (values.next(), values.next()) {
-            (Some(value), None) => {
-                if value == "false" {
-                    Ok(Self::NoChange)
-                } else {
-                    value
-                        .as_bytes()
-                        .try_into()
-                        .map(Self::Uri)
-                        .map_err(|_| headers_core::Error::invalid())
-                }
-            }
+            (Some(value), None) => Ok(value.try_into()?),
             _ => Err(headers_core::Error::invalid()),
         }
     }
 
-    /// NOTE: Panics if the value cannot be converted to a header value.
+    /// a [`Uri`] is always a valid header value, so this never panics.
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
-        let header = match self {
-            Self::Uri(uri) => HeaderValue::from_str(&uri.to_string()).unwrap(),
-            Self::NoChange => HeaderValue::from_static("false"),
-            Self::Phantom(_) => return,
+        let header = match &self.value {
+            HistoryValue::Uri(uri) => HeaderValue::from_str(&uri.to_string()).unwrap(),
+            HistoryValue::NoChange => HeaderValue::from_static("false"),
         };
 
         values.extend(std::iter::once(header));
@@ -204,8 +525,15 @@ define_header! {
     /// allows you to specify how the response will be swapped. See [hx-swap](https://htmx.org/attributes/hx-swap/) for possible values
     (HX_RESWAP, "hx-reswap")
 
-    #[derive(Copy)]
-    pub struct HxReswap(pub Swap);
+    pub struct HxReswap(pub SwapStyle);
+}
+
+impl HxReswap {
+    /// parse the value of an element's `hx-swap` attribute, including its
+    /// modifiers, to echo it in a response.
+    pub fn from_attribute(attr: &str) -> Result<Self, ParseSwapStyleError> {
+        attr.parse().map(Self)
+    }
 }
 
 impl Header for HxReswap {
@@ -219,29 +547,143 @@ impl Header for HxReswap {
         I: Iterator<Item = &'i HeaderValue>,
     {
         match (values.next(), values.next()) {
-            (Some(value), None) => value
-                .as_bytes()
-                .try_into()
+            (Some(value), None) => std::str::from_utf8(value.as_bytes())
+                .ok()
+                .and_then(|s| s.parse().ok())
                 .map(Self)
-                .map_err(|()| headers_core::Error::invalid()),
+                .ok_or_else(headers_core::Error::invalid),
             _ => Err(headers_core::Error::invalid()),
         }
     }
 
     /// NOTE: Panics if the value cannot be converted to a header value.
     fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
-        values.extend(std::iter::once(self.0.into()));
+        self.try_encode(values).unwrap();
+    }
+}
+
+impl Display for HxReswap {
+    /// formats the header value, as [`Header::encode`] would encode it.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
     }
 }
 
-string_header! {
+impl HxReswap {
+    /// encode the header like [`Header::encode`], but fail instead of
+    /// panicking if a selector is not a valid header value.
+    pub fn try_encode<E: Extend<HeaderValue>>(
+        &self,
+        values: &mut E,
+    ) -> Result<(), http::header::InvalidHeaderValue> {
+        values.extend(std::iter::once(HeaderValue::try_from(&self.0)?));
+        Ok(())
+    }
+}
+
+convert_header! {
     /// a CSS selector that updates the target of the content update to a different element on the page
-    (HX_RETARGET, HxRetarget, "hx-retarget")
+    CssSelector => (HX_RETARGET, HxRetarget, "hx-retarget")
 }
 
-string_header! {
+convert_header! {
     /// a CSS selector that allows you to choose which part of the response is used to be swapped in. Overrides an existing [hx-select](https://htmx.org/attributes/hx-select/) on the triggering element
-    (HX_RESELECT, HxReselect, "hx-reselect")
+    CssSelector => (HX_RESELECT, HxReselect, "hx-reselect")
+}
+
+impl HxReselect {
+    /// whether a response needs [`HxReselect`] to pick out the part to swap.
+    ///
+    /// a response which already renders exactly the fragment to swap, rather
+    /// than a `full_document`, can be swapped in whole.
+    #[must_use]
+    pub const fn needs_reselect(full_document: bool) -> bool {
+        full_document
+    }
+}
+
+/// the swap-related response headers, decoded at once.
+///
+/// useful for simulating how the client will swap a response.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapInstruction {
+    /// the [`HxReswap`] header
+    pub reswap: Option<HxReswap>,
+
+    /// the [`HxRetarget`] header
+    pub retarget: Option<HxRetarget>,
+
+    /// the [`HxReselect`] header
+    pub reselect: Option<HxReselect>,
+}
+
+impl SwapInstruction {
+    /// decode the swap-related headers from a response's `map`.
+    ///
+    /// fails if any header is present but invalid.
+    pub fn from_response(map: &http::HeaderMap) -> Result<Self, InvalidHeader> {
+        Ok(Self {
+            reswap: decode_optional(map)?,
+            retarget: decode_optional(map)?,
+            reselect: decode_optional(map)?,
+        })
+    }
+}
+
+/// the swap htmx will perform for a response, after applying the
+/// [`SwapInstruction`] headers over the request's own settings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EffectiveSwap {
+    target: Option<String>,
+    swap: Option<SwapStyle>,
+    select: Option<String>,
+}
+
+impl EffectiveSwap {
+    /// resolve the swap for a response with headers `map`, to a request whose
+    /// target is the selector `request_target`.
+    ///
+    /// [`HxRetarget`] overrides `request_target`, while [`HxReswap`] and
+    /// [`HxReselect`] override the triggering element's `hx-swap` and
+    /// `hx-select`.
+    pub fn from_response(
+        request_target: Option<&str>,
+        map: &http::HeaderMap,
+    ) -> Result<Self, InvalidHeader> {
+        let SwapInstruction {
+            reswap,
+            retarget,
+            reselect,
+        } = SwapInstruction::from_response(map)?;
+
+        Ok(Self {
+            target: retarget
+                .map(|HxRetarget(target)| target.as_str().to_owned())
+                .or_else(|| request_target.map(ToOwned::to_owned)),
+            swap: reswap.map(|HxReswap(swap)| swap),
+            select: reselect.map(|HxReselect(select)| select.as_str().to_owned()),
+        })
+    }
+
+    /// the selector of the element to swap into, or `None` if neither the
+    /// request nor the response specify one.
+    #[must_use]
+    pub fn target(&self) -> Option<&str> {
+        self.target.as_deref()
+    }
+
+    /// the swap style, or `None` to use the triggering element's.
+    #[must_use]
+    pub const fn swap(&self) -> Option<&SwapStyle> {
+        self.swap.as_ref()
+    }
+
+    /// the selector of the part of the response to swap in, or `None` to use
+    /// the triggering element's.
+    #[must_use]
+    pub fn select(&self) -> Option<&str> {
+        self.select.as_deref()
+    }
 }
 
 define_header! {
@@ -250,15 +692,181 @@ define_header! {
     /// [htmx docs](https://htmx.org/headers/hx-trigger/)
     (HX_TRIGGER, "hx-trigger")
 
-    pub enum HxTrigger<After: TriggerAfter = ()> {
-        /// a list of events to trigger
-        List(Vec<String>),
+    pub struct HxTrigger<After: TriggerAfter = ()> {
+        triggers: Triggers,
+        _marker: PhantomData<After>,
+    }
+}
+
+/// the events of an [`HxTrigger`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Triggers {
+    /// a list of events to trigger
+    List(Vec<EventName>),
+
+    /// events to trigger with details, in the order htmx dispatches them
+    #[cfg(feature = "serde")]
+    WithDetails(Vec<(EventName, serde_json::Value)>),
+}
+
+/// the name of an event in an [`HxTrigger`].
+///
+/// names cannot be empty, contain commas, or start or end with whitespace,
+/// since a [`Triggers::List`] is comma separated and trimmed when decoded.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(try_from = "String", into = "String")
+)]
+pub struct EventName(String);
+
+impl EventName {
+    /// create an event name, checking that it survives a comma separated list
+    /// and is a valid header value.
+    pub fn new(name: impl Into<String>) -> Result<Self, InvalidEventName> {
+        let name = name.into();
+
+        if name.is_empty()
+            || name.contains(',')
+            || name.trim() != name
+            || HeaderValue::from_str(&name).is_err()
+        {
+            Err(InvalidEventName { name })
+        } else {
+            Ok(Self(name))
+        }
+    }
+
+    /// the name as a string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl FromStr for EventName {
+    type Err = InvalidEventName;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl TryFrom<String> for EventName {
+    type Error = InvalidEventName;
+
+    fn try_from(name: String) -> Result<Self, Self::Error> {
+        Self::new(name)
+    }
+}
+
+impl TryFrom<&str> for EventName {
+    type Error = InvalidEventName;
+
+    fn try_from(name: &str) -> Result<Self, Self::Error> {
+        Self::new(name)
+    }
+}
+
+impl From<EventName> for String {
+    fn from(EventName(name): EventName) -> Self {
+        name
+    }
+}
+
+impl AsRef<str> for EventName {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for EventName {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl Display for EventName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// an error returned when [`EventName::new`] rejects a name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidEventName {
+    name: String,
+}
+
+impl InvalidEventName {
+    /// the name which was rejected.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl Display for InvalidEventName {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid event name: `{}`", self.name)
+    }
+}
+
+impl std::error::Error for InvalidEventName {}
 
-        /// a map of events to trigger with details
-        WithDetails(HashMap<String, serde_json::Value>),
-        #[doc(hidden)]
-        #[allow(dead_code)]
-        Phantom(std::marker::PhantomData<After>),
+impl<After: TriggerAfter> From<Triggers> for HxTrigger<After> {
+    fn from(triggers: Triggers) -> Self {
+        Self::new(triggers)
+    }
+}
+
+/// the details of an event in a [`Triggers::WithDetails`].
+#[cfg(feature = "serde")]
+pub type EventDetail = serde_json::Value;
+
+/// the details of an event, which cannot be constructed without the `serde`
+/// feature, as [`HxTrigger`] is then list-only.
+#[cfg(not(feature = "serde"))]
+pub type EventDetail = std::convert::Infallible;
+
+/// an application event which can be triggered on the client with
+/// [`HxTrigger::from_domain_events`].
+#[cfg(feature = "serde")]
+pub trait HtmxEvent {
+    /// the name of the client-side event.
+    fn event_name(&self) -> &str;
+}
+
+/// an error returned when [`HxTrigger::from_domain_events`] cannot turn a
+/// domain event into a trigger.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum DomainEventError {
+    /// the event's name is not a valid [`EventName`].
+    InvalidName(InvalidEventName),
+
+    /// the event could not be serialized to JSON.
+    Serialize(serde_json::Error),
+}
+
+#[cfg(feature = "serde")]
+impl Display for DomainEventError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName(err) => err.fmt(f),
+            Self::Serialize(err) => write!(f, "failed to serialize event: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for DomainEventError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidName(err) => Some(err),
+            Self::Serialize(err) => Some(err),
+        }
     }
 }
 
@@ -306,79 +914,2011 @@ impl TriggerAfter for AfterSwap {
     }
 }
 
-impl<After: TriggerAfter> Header for HxTrigger<After> {
-    fn name() -> &'static HeaderName {
-        After::name()
+impl<After: TriggerAfter> HxTrigger<After> {
+    /// create the header from its [`Triggers`].
+    #[must_use]
+    pub const fn new(triggers: Triggers) -> Self {
+        Self {
+            triggers,
+            _marker: PhantomData,
+        }
     }
 
-    fn decode<'i, I>(values: &mut I) -> Result<Self, headers_core::Error>
-    where
-        Self: Sized,
-        I: Iterator<Item = &'i HeaderValue>,
-    {
-        match (values.next(), values.next()) {
-            (Some(value), None) => {
-                let bytes = value.as_bytes();
-                serde_json::from_slice(bytes)
-                    .map(Self::WithDetails)
-                    .or_else(|_| {
-                        let items = value
-                            .to_str()
-                            .map_err(|_| headers_core::Error::invalid())?
-                            .split(',')
-                            .map(|s| s.trim().to_owned())
-                            .collect();
-
-                        Ok(Self::List(items))
-                    })
-            }
-            _ => Err(headers_core::Error::invalid()),
-        }
+    /// trigger a [`Triggers::List`] of events.
+    #[must_use]
+    pub fn list(events: impl IntoIterator<Item = EventName>) -> Self {
+        Self::new(Triggers::List(events.into_iter().collect()))
     }
 
-    /// NOTE: Panics if the value cannot be converted to a header value.
-    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
-        let val = match self {
-            Self::List(list) => {
-                let s = list.join(", ");
-                HeaderValue::from_str(&s).unwrap()
-            }
-            Self::WithDetails(details) => {
-                let s = serde_json::to_string(details).unwrap();
-                HeaderValue::from_str(&s).unwrap()
-            }
-            Self::Phantom(_) => return,
-        };
+    /// trigger [`Triggers::WithDetails`] events.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub const fn with_details(events: Vec<(EventName, EventDetail)>) -> Self {
+        Self::new(Triggers::WithDetails(events))
+    }
 
-        values.extend(std::iter::once(val));
+    /// trigger a single event, without details.
+    ///
+    /// fails if `event` is not a valid [`EventName`].
+    pub fn single(event: impl Into<String>) -> Result<Self, InvalidEventName> {
+        Self::events([event])
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// trigger a list of events, without details.
+    ///
+    /// fails on the first event which is not a valid [`EventName`].
+    pub fn events(
+        events: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Result<Self, InvalidEventName> {
+        events
+            .into_iter()
+            .map(EventName::new)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::list)
+    }
 
-    #[test]
-    fn trigger_works() {
-        let val = HeaderValue::from_static(r#"{"event1":"A message", "event2":"Another message"}"#);
+    /// trigger a single event with a JSON `detail`.
+    ///
+    /// fails if `event` is not a valid [`EventName`].
+    #[cfg(feature = "serde")]
+    pub fn detail(event: impl Into<String>, detail: EventDetail) -> Result<Self, InvalidEventName> {
+        Ok(Self::with_details(vec![(EventName::new(event)?, detail)]))
+    }
 
-        claims::assert_ok_eq!(
-            HxTrigger::<()>::decode(&mut std::iter::once(&val)),
-            HxTrigger::WithDetails(
+    /// the events to trigger.
+    #[must_use]
+    pub const fn triggers(&self) -> &Triggers {
+        &self.triggers
+    }
+
+    /// the events to trigger, mutably.
+    pub const fn triggers_mut(&mut self) -> &mut Triggers {
+        &mut self.triggers
+    }
+
+    /// take the events to trigger.
+    #[must_use]
+    pub fn into_triggers(self) -> Triggers {
+        self.triggers
+    }
+
+    /// start building a [`Triggers::WithDetails`] event by event.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub const fn builder() -> HxTriggerBuilder<After> {
+        HxTriggerBuilder {
+            events: Vec::new(),
+            after: PhantomData,
+        }
+    }
+
+    /// build a [`Triggers::WithDetails`] from domain events, using each
+    /// event's [`HtmxEvent::event_name`] as the name and its serialization
+    /// as the details.
+    ///
+    /// fails on the first event whose name is not a valid [`EventName`], or
+    /// which cannot be serialized to JSON.
+    #[cfg(feature = "serde")]
+    pub fn from_domain_events<E: HtmxEvent + Serialize>(
+        events: impl IntoIterator<Item = E>,
+    ) -> Result<Self, DomainEventError> {
+        events
+            .into_iter()
+            .map(|event| {
+                Ok((
+                    EventName::new(event.event_name()).map_err(DomainEventError::InvalidName)?,
+                    serde_json::to_value(&event).map_err(DomainEventError::Serialize)?,
+                ))
+            })
+            .collect::<Result<_, _>>()
+            .map(Self::with_details)
+    }
+
+    /// trigger a single event with a typed `detail`, serialized as
+    /// `{"<event>": <detail>}`.
+    ///
+    /// read it back with [`parse_detail`](Self::parse_detail).
+    ///
+    /// fails if `detail` cannot be serialized to JSON.
+    #[cfg(feature = "serde")]
+    pub fn with_detail<T: Serialize>(
+        event: EventName,
+        detail: &T,
+    ) -> Result<Self, serde_json::Error> {
+        Ok(Self::with_details(vec![(
+            event,
+            serde_json::to_value(detail)?,
+        )]))
+    }
+
+    /// deserialize the detail of the first event named `event` into `T`.
+    ///
+    /// returns `None` if there is no such event with a detail.
+    #[cfg(feature = "serde")]
+    pub fn parse_detail<T: DeserializeOwned>(
+        &self,
+        event: &str,
+    ) -> Option<Result<T, serde_json::Error>> {
+        self.iter_events()
+            .find_map(|(name, detail)| detail.filter(|_| name == event))
+            .map(T::deserialize)
+    }
+
+    /// build a [`Triggers::WithDetails`] event named `event_name` echoing the
+    /// user's response to an [`hx-prompt`](https://htmx.org/attributes/hx-prompt/)
+    /// as its `prompt` detail.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn acknowledge_prompt(event_name: EventName, prompt: &HxPrompt) -> Self {
+        let HxPrompt(prompt) = prompt;
+        Self::with_details(vec![(event_name, serde_json::json!({ "prompt": prompt }))])
+    }
+
+    /// encode the header like [`Header::encode`], but fail instead of
+    /// panicking if an event name or detail is not a valid header value.
+    pub fn try_encode<E: Extend<HeaderValue>>(
+        &self,
+        values: &mut E,
+    ) -> Result<(), http::header::InvalidHeaderValue> {
+        let val = match &self.triggers {
+            Triggers::List(list) => {
+                let names = list.iter().map(EventName::as_str).collect::<Vec<_>>();
+                HeaderValue::from_str(&names.join(", "))?
+            }
+            #[cfg(feature = "serde")]
+            Triggers::WithDetails(details) => json_header_value(&MapRef(details))?,
+        };
+
+        values.extend(std::iter::once(val));
+        Ok(())
+    }
+
+    /// append this trigger to `map`, keeping any existing values.
+    ///
+    /// each event of a [`Triggers::List`] is appended as its own header
+    /// value, while [`Triggers::WithDetails`] is appended as a single JSON
+    /// value.
+    ///
+    /// NOTE: Panics if a value cannot be converted to a header value.
+    pub fn append_to(&self, map: &mut http::HeaderMap) {
+        match &self.triggers {
+            Triggers::List(list) => {
+                for event in list {
+                    map.append(
+                        After::name(),
+                        HeaderValue::from_str(event.as_str()).unwrap(),
+                    );
+                }
+            }
+            #[cfg(feature = "serde")]
+            Triggers::WithDetails(details) => {
+                map.append(After::name(), json_header_value(&MapRef(details)).unwrap());
+            }
+        }
+    }
+
+    /// append this trigger to `map` like [`append_to`](Self::append_to), but
+    /// pack a [`Triggers::List`] into comma separated values of at most
+    /// `max_len` bytes, to stay under proxy header size limits.
+    ///
+    /// an event longer than `max_len` gets a value of its own, and
+    /// [`Triggers::WithDetails`] is still appended as a single JSON value.
+    ///
+    /// NOTE: Panics if a value cannot be converted to a header value.
+    pub fn append_split_to(&self, map: &mut http::HeaderMap, max_len: usize) {
+        #[cfg(feature = "serde")]
+        if matches!(self.triggers, Triggers::WithDetails(_)) {
+            return self.append_to(map);
+        }
+
+        let mut line = String::new();
+        for (event, _) in self.iter_events() {
+            if !line.is_empty() && line.len() + ", ".len() + event.len() > max_len {
+                map.append(After::name(), HeaderValue::from_str(&line).unwrap());
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push_str(", ");
+            }
+            line.push_str(event);
+        }
+
+        if !line.is_empty() {
+            map.append(After::name(), HeaderValue::from_str(&line).unwrap());
+        }
+    }
+
+    /// iterate over each event's name and, for [`Triggers::WithDetails`], its
+    /// details.
+    pub fn iter_events(&self) -> impl Iterator<Item = (&str, Option<&EventDetail>)> {
+        let (list, details): (_, Option<&Vec<(EventName, EventDetail)>>) = match &self.triggers {
+            Triggers::List(list) => (Some(list), None),
+            #[cfg(feature = "serde")]
+            Triggers::WithDetails(details) => (None, Some(details)),
+        };
+
+        list.into_iter()
+            .flatten()
+            .map(|event| (event.as_str(), None))
+            .chain(
+                details
+                    .into_iter()
+                    .flatten()
+                    .map(|(event, detail)| (event.as_str(), Some(detail))),
+            )
+    }
+
+    /// keep only the events for which `f` returns `true`, preserving order.
+    ///
+    /// `f` is given each event's name and, for [`Triggers::WithDetails`], its
+    /// details.
+    pub fn retain(&mut self, mut f: impl FnMut(&str, Option<&EventDetail>) -> bool) {
+        match &mut self.triggers {
+            Triggers::List(list) => list.retain(|event| f(event.as_str(), None)),
+            #[cfg(feature = "serde")]
+            Triggers::WithDetails(details) => {
+                details.retain(|(event, detail)| f(event.as_str(), Some(detail)));
+            }
+        }
+    }
+
+    /// remove repeated event names, keeping the first occurrence of each.
+    ///
+    /// htmx fires an event once per occurrence, which is usually unintended.
+    /// the removed names are returned so callers can warn about them.
+    pub fn dedup(&mut self) -> Vec<String> {
+        let mut seen = HashSet::new();
+        let mut removed = Vec::new();
+
+        self.retain(|event, _| {
+            let first = seen.insert(event.to_owned());
+            if !first {
+                removed.push(event.to_owned());
+            }
+            first
+        });
+
+        removed
+    }
+}
+
+/// a builder for [`Triggers::WithDetails`], created by [`HxTrigger::builder`].
+///
+/// each call to [`event`](Self::event) starts an event, returning a
+/// [`HxTriggerEventBuilder`] which adds details to it.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HxTriggerBuilder<After: TriggerAfter = ()> {
+    events: Vec<(EventName, serde_json::Value)>,
+    after: PhantomData<After>,
+}
+
+#[cfg(feature = "serde")]
+impl<After: TriggerAfter> HxTriggerBuilder<After> {
+    /// start an event to trigger.
+    #[must_use]
+    pub fn event(self, name: EventName) -> HxTriggerEventBuilder<After> {
+        HxTriggerEventBuilder {
+            builder: self,
+            name,
+            details: serde_json::Map::new(),
+            error: None,
+        }
+    }
+}
+
+/// a builder for the details of one event of a [`HxTriggerBuilder`], created
+/// by [`HxTriggerBuilder::event`].
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct HxTriggerEventBuilder<After: TriggerAfter = ()> {
+    builder: HxTriggerBuilder<After>,
+    name: EventName,
+    details: serde_json::Map<String, serde_json::Value>,
+    error: Option<serde_json::Error>,
+}
+
+#[cfg(feature = "serde")]
+impl<After: TriggerAfter> HxTriggerEventBuilder<After> {
+    /// add a detail to this event.
+    ///
+    /// if `value` cannot be serialized to JSON, [`build`](Self::build) fails.
+    #[must_use]
+    pub fn detail(mut self, key: impl Into<String>, value: impl Serialize) -> Self {
+        if self.error.is_none() {
+            match serde_json::to_value(value) {
+                Ok(value) => {
+                    self.details.insert(key.into(), value);
+                }
+                Err(err) => self.error = Some(err),
+            }
+        }
+
+        self
+    }
+
+    /// finish this event and start another.
+    #[must_use]
+    pub fn event(self, name: EventName) -> Self {
+        let error = self.error;
+        let mut builder = self.builder;
+        builder
+            .events
+            .push((self.name, serde_json::Value::Object(self.details)));
+
+        Self {
+            error,
+            ..builder.event(name)
+        }
+    }
+
+    /// build the trigger header, failing with the first detail which could not
+    /// be serialized.
+    pub fn build(self) -> Result<HxTrigger<After>, serde_json::Error> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let mut events = self.builder.events;
+        events.push((self.name, serde_json::Value::Object(self.details)));
+        Ok(HxTrigger::with_details(events))
+    }
+}
+
+impl<After: TriggerAfter> Display for HxTrigger<After> {
+    /// formats the header value, as [`Header::encode`] would encode it.
+    ///
+    /// [`append_to`](Self::append_to) writes a list as separate values
+    /// instead.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.triggers {
+            Triggers::List(list) => {
+                for (i, event) in list.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    f.write_str(event.as_str())?;
+                }
+
+                Ok(())
+            }
+            #[cfg(feature = "serde")]
+            Triggers::WithDetails(details) => {
+                f.write_str(&serde_json::to_string(&MapRef(details)).map_err(|_| fmt::Error)?)
+            }
+        }
+    }
+}
+
+impl<After: TriggerAfter> Header for HxTrigger<After> {
+    fn name() -> &'static HeaderName {
+        After::name()
+    }
+
+    fn decode<'i, I>(values: &mut I) -> Result<Self, headers_core::Error>
+    where
+        Self: Sized,
+        I: Iterator<Item = &'i HeaderValue>,
+    {
+        fn split_list(value: &HeaderValue) -> Result<Vec<EventName>, headers_core::Error> {
+            std::str::from_utf8(value.as_bytes())
+                .map_err(|_| headers_core::Error::invalid())?
+                .split(',')
+                .map(|s| EventName::new(s.trim()).map_err(|_| headers_core::Error::invalid()))
+                .collect()
+        }
+
+        // a value starting with `{` or `[` can only be a JSON object or array,
+        // so it is never split as a list, even if it fails to parse.
+        fn is_object(value: &HeaderValue) -> bool {
+            value.as_bytes().trim_ascii_start().starts_with(b"{")
+        }
+
+        fn is_array(value: &HeaderValue) -> bool {
+            value.as_bytes().trim_ascii_start().starts_with(b"[")
+        }
+
+        match values.collect::<Vec<_>>().as_slice() {
+            [] => Err(headers_core::Error::invalid()),
+            #[cfg(feature = "serde")]
+            [value] if is_object(value) => serde_json::from_slice(value.as_bytes())
+                .map(|OrderedMap(details)| Self::with_details(details))
+                .map_err(|_| headers_core::Error::invalid()),
+            #[cfg(feature = "serde")]
+            [value] if is_array(value) => serde_json::from_slice::<Vec<_>>(value.as_bytes())
+                .map(Self::list)
+                .map_err(|_| headers_core::Error::invalid()),
+            #[cfg(not(feature = "serde"))]
+            [value] if is_object(value) || is_array(value) => Err(headers_core::Error::invalid()),
+            [value] => split_list(value).map(Self::list),
+            // multiple lines, as written by `append_to`, can only be lists.
+            values => {
+                let mut items = Vec::new();
+                for value in values {
+                    if is_object(value) || is_array(value) {
+                        return Err(headers_core::Error::invalid());
+                    }
+
+                    items.extend(split_list(value)?);
+                }
+
+                Ok(Self::list(items))
+            }
+        }
+    }
+
+    /// NOTE: Panics if the value cannot be converted to a header value.
+    fn encode<E: Extend<HeaderValue>>(&self, values: &mut E) {
+        self.try_encode(values).unwrap();
+    }
+}
+
+/// a confirm dialog flow, reading the user's answer to an
+/// [`hx-prompt`](https://htmx.org/attributes/hx-prompt/) and responding with
+/// an event accepting or rejecting it.
+///
+/// a missing or empty prompt is always rejected.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Confirmation {
+    prompt: Option<HxPrompt>,
+}
+
+#[cfg(feature = "serde")]
+impl Confirmation {
+    /// read the prompt from the request's [`HxPrompt`] header.
+    #[must_use]
+    pub fn from_request(headers: &RequestHeaders) -> Self {
+        Self {
+            prompt: headers
+                .prompt
+                .clone()
+                .filter(|HxPrompt(prompt)| !prompt.is_empty()),
+        }
+    }
+
+    /// the user's non-empty answer to the prompt.
+    #[must_use]
+    pub fn prompt(&self) -> Option<&str> {
+        self.prompt.as_ref().map(|HxPrompt(prompt)| prompt.as_str())
+    }
+
+    /// respond with `accepted` if there is a prompt and `decide` approves it,
+    /// or with `rejected` otherwise.
+    ///
+    /// `accepted` echoes the prompt as its detail, like
+    /// [`HxTrigger::acknowledge_prompt`].
+    pub fn respond<After: TriggerAfter>(
+        &self,
+        accepted: EventName,
+        rejected: EventName,
+        decide: impl FnOnce(&str) -> bool,
+    ) -> HxTrigger<After> {
+        match &self.prompt {
+            Some(prompt) if decide(&prompt.0) => HxTrigger::acknowledge_prompt(accepted, prompt),
+            _ => HxTrigger::list([rejected]),
+        }
+    }
+}
+
+/// any combination of htmx response headers, to be applied to a response at
+/// once.
+///
+/// each header is only applied if it is `Some`.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct HtmxResponseHeaders {
+    /// the [`HxLocation`] header
+    #[cfg(feature = "serde")]
+    pub location: Option<HxLocation>,
+
+    /// the [`HxPushUrl`] header
+    pub push_url: Option<HxModifyHistory<HxPushUrl>>,
+
+    /// the [`HxRedirect`] header
+    pub redirect: Option<HxRedirect>,
+
+    /// the [`HxRefresh`] header
+    pub refresh: Option<HxRefresh>,
+
+    /// the [`HxReplaceUrl`] header
+    pub replace_url: Option<HxModifyHistory<HxReplaceUrl>>,
+
+    /// the [`HxReswap`] header
+    pub reswap: Option<HxReswap>,
+
+    /// the [`HxRetarget`] header
+    pub retarget: Option<HxRetarget>,
+
+    /// the [`HxReselect`] header
+    pub reselect: Option<HxReselect>,
+
+    /// the [`HxTrigger`] header
+    pub trigger: Option<HxTrigger>,
+
+    /// the [`HxTrigger<AfterSettle>`] header
+    pub trigger_after_settle: Option<HxTrigger<AfterSettle>>,
+
+    /// the [`HxTrigger<AfterSwap>`] header
+    pub trigger_after_swap: Option<HxTrigger<AfterSwap>>,
+}
+
+/// what is known about the element and body a response is for, used by
+/// [`HtmxResponseHeaders::apply_to_with`] to leave out headers which would
+/// have no effect.
+///
+/// the default knows nothing, so every set header is applied.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ApplyContext {
+    /// the swap style the target element already specifies, if known
+    ///
+    /// `reswap` is not applied if it matches this, since it would be
+    /// redundant. elements without an `hx-swap` attribute use
+    /// [`Swap::InnerHtml`] (see [`Swap::is_default`]).
+    pub element_swap: Option<SwapStyle>,
+
+    /// whether the body is already the fragment to swap
+    ///
+    /// `reselect` is not applied if so, since there is nothing to select
+    /// from (see [`HxReselect::needs_reselect`]).
+    pub body_is_fragment: bool,
+}
+
+impl ApplyContext {
+    /// create a context which knows nothing about the element or body.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// set the swap style the target element already specifies.
+    #[must_use]
+    pub fn element_swap(mut self, style: impl Into<SwapStyle>) -> Self {
+        self.element_swap = Some(style.into());
+        self
+    }
+
+    /// mark the body as already being the fragment to swap, so `reselect` is
+    /// not applied.
+    #[must_use]
+    pub const fn body_is_fragment(mut self, fragment: bool) -> Self {
+        self.body_is_fragment = fragment;
+        self
+    }
+}
+
+impl HtmxResponseHeaders {
+    /// create an empty set of response headers.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// set the [`HxLocation`] header.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn location(mut self, location: impl Into<HxLocation>) -> Self {
+        self.location = Some(location.into());
+        self
+    }
+
+    /// set the [`HxPushUrl`] header to `uri`.
+    #[must_use]
+    pub fn push_url(mut self, uri: Uri) -> Self {
+        self.push_url = Some(HxModifyHistory::uri(uri));
+        self
+    }
+
+    /// set the [`HxRedirect`] header to `uri`.
+    #[must_use]
+    pub fn redirect(mut self, uri: Uri) -> Self {
+        self.redirect = Some(HxRedirect(uri));
+        self
+    }
+
+    /// set the [`HxRefresh`] header.
+    #[must_use]
+    pub const fn refresh(mut self) -> Self {
+        self.refresh = Some(HxRefresh);
+        self
+    }
+
+    /// set the [`HxReplaceUrl`] header to `uri`.
+    #[must_use]
+    pub fn replace_url(mut self, uri: Uri) -> Self {
+        self.replace_url = Some(HxModifyHistory::uri(uri));
+        self
+    }
+
+    /// set the [`HxReswap`] header.
+    #[must_use]
+    pub fn reswap(mut self, style: impl Into<SwapStyle>) -> Self {
+        self.reswap = Some(HxReswap(style.into()));
+        self
+    }
+
+    /// set the [`HxRetarget`] header to `selector`.
+    #[must_use]
+    pub fn retarget(mut self, selector: impl Into<CssSelector>) -> Self {
+        self.retarget = Some(HxRetarget(selector.into()));
+        self
+    }
+
+    /// set the [`HxReselect`] header to `selector`.
+    #[must_use]
+    pub fn reselect(mut self, selector: impl Into<CssSelector>) -> Self {
+        self.reselect = Some(HxReselect(selector.into()));
+        self
+    }
+
+    /// set the [`HxTrigger`] header to a list of `events`.
+    #[must_use]
+    pub fn trigger(mut self, events: impl IntoIterator<Item = EventName>) -> Self {
+        self.trigger = Some(HxTrigger::list(events));
+        self
+    }
+
+    /// set the [`HxTrigger<AfterSettle>`] header to a list of `events`.
+    #[must_use]
+    pub fn trigger_after_settle(mut self, events: impl IntoIterator<Item = EventName>) -> Self {
+        self.trigger_after_settle = Some(HxTrigger::list(events));
+        self
+    }
+
+    /// set the [`HxTrigger<AfterSwap>`] header to a list of `events`.
+    #[must_use]
+    pub fn trigger_after_swap(mut self, events: impl IntoIterator<Item = EventName>) -> Self {
+        self.trigger_after_swap = Some(HxTrigger::list(events));
+        self
+    }
+
+    /// encode every set header into `map`, replacing any existing values.
+    ///
+    /// NOTE: Panics if a header is not a valid header value. use
+    /// [`try_apply_to`](Self::try_apply_to) to fail instead.
+    pub fn apply_to(&self, map: &mut http::HeaderMap) {
+        self.apply_to_with(map, &ApplyContext::default());
+    }
+
+    /// like [`apply_to`](Self::apply_to), but leave out headers which would
+    /// have no effect given `context`.
+    ///
+    /// NOTE: Panics if an applied header is not a valid header value.
+    pub fn apply_to_with(&self, map: &mut http::HeaderMap, context: &ApplyContext) {
+        fn apply<H: Header>(map: &mut http::HeaderMap, header: Option<&H>) {
+            if let Some(header) = header {
+                insert_encoded(map, header);
+            }
+        }
+
+        #[cfg(feature = "serde")]
+        apply(map, self.location.as_ref());
+        apply(map, self.push_url.as_ref());
+        apply(map, self.redirect.as_ref());
+        apply(map, self.refresh.as_ref());
+        apply(map, self.replace_url.as_ref());
+        apply(
+            map,
+            self.reswap
+                .as_ref()
+                .filter(|HxReswap(swap)| Some(swap) != context.element_swap.as_ref()),
+        );
+        apply(map, self.retarget.as_ref());
+        apply(
+            map,
+            self.reselect
+                .as_ref()
+                .filter(|_| HxReselect::needs_reselect(!context.body_is_fragment)),
+        );
+        apply(map, self.trigger.as_ref());
+        apply(map, self.trigger_after_settle.as_ref());
+        apply(map, self.trigger_after_swap.as_ref());
+    }
+
+    /// like [`apply_to`](Self::apply_to), but fail with every problem found
+    /// by [`validate`](Self::validate) instead of panicking.
+    ///
+    /// `map` is left untouched on failure.
+    pub fn try_apply_to(&self, map: &mut http::HeaderMap) -> Result<(), Vec<HeaderProblem>> {
+        self.validate()?;
+        self.apply_to(map);
+        Ok(())
+    }
+
+    /// check every set header for problems which would make it fail to encode,
+    /// decode differently than it was set, or be ignored by htmx.
+    pub fn validate(&self) -> Result<(), Vec<HeaderProblem>> {
+        fn header_safe(s: &str) -> bool {
+            HeaderValue::from_str(s).is_ok()
+        }
+
+        fn check_selectors<'a>(
+            problems: &mut Vec<HeaderProblem>,
+            header: &'static HeaderName,
+            selectors: impl IntoIterator<Item = &'a str>,
+        ) {
+            for selector in selectors {
+                let reason = if !header_safe(selector) {
+                    "selector is not a valid header value"
+                } else if CssSelector::new(selector).is_err() {
+                    "selector is malformed"
+                } else {
+                    continue;
+                };
+
+                problems.push(HeaderProblem::new(header, reason));
+                return;
+            }
+        }
+
+        fn swap_selectors(style: &SwapStyle) -> impl Iterator<Item = &str> {
+            [&style.scroll, &style.show]
+                .into_iter()
+                .flatten()
+                .filter_map(|modifier| modifier.selector.as_ref())
+                .map(CssSelector::as_str)
+        }
+
+        let mut problems = Vec::new();
+
+        #[cfg(feature = "serde")]
+        if let Some(context) = self.location.as_ref().and_then(|l| l.context.as_ref()) {
+            check_selectors(
+                &mut problems,
+                &HX_LOCATION,
+                [&context.target, &context.select]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .chain(context.swap.iter().flat_map(swap_selectors)),
+            );
+        }
+
+        if let Some(HxReswap(style)) = &self.reswap {
+            check_selectors(&mut problems, &HX_RESWAP, swap_selectors(style));
+        }
+
+        if let Some(HxRetarget(selector)) = &self.retarget {
+            check_selectors(&mut problems, &HX_RETARGET, [selector.as_str()]);
+        }
+
+        if let Some(HxReselect(selector)) = &self.reselect {
+            check_selectors(&mut problems, &HX_RESELECT, [selector.as_str()]);
+
+            if let Some(HxReswap(SwapStyle {
+                style: Swap::Delete | Swap::None,
+                ..
+            })) = self.reswap
+            {
+                problems.push(HeaderProblem::new(
+                    &HX_RESELECT,
+                    "reselect has no effect when swapping with `delete` or `none`",
+                ));
+            }
+        }
+
+        // anything not already reported must still encode, or `apply_to` would
+        // panic on it.
+        macro_rules! check_encode {
+            ($header:expr, $name:expr) => {
+                if let Some(header) = &$header {
+                    if !problems.iter().any(|problem| problem.header == $name)
+                        && header.try_encode(&mut Vec::new()).is_err()
+                    {
+                        problems.push(HeaderProblem::new(
+                            $name,
+                            "value is not a valid header value",
+                        ));
+                    }
+                }
+            };
+        }
+
+        #[cfg(feature = "serde")]
+        check_encode!(self.location, &HX_LOCATION);
+        check_encode!(self.redirect, &HX_REDIRECT);
+        check_encode!(self.reswap, &HX_RESWAP);
+        check_encode!(self.retarget, &HX_RETARGET);
+        check_encode!(self.reselect, &HX_RESELECT);
+        check_encode!(self.trigger, &HX_TRIGGER);
+        check_encode!(self.trigger_after_settle, &HX_TRIGGER_AFTER_SETTLE);
+        check_encode!(self.trigger_after_swap, &HX_TRIGGER_AFTER_SWAP);
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+
+    /// drop headers that htmx would ignore or misinterpret.
+    ///
+    /// currently, this removes [`HxReselect`] when [`HxReswap`] is
+    /// [`Swap::Delete`] or [`Swap::None`], returning the removed header so
+    /// callers can warn about it.
+    pub const fn normalize(&mut self) -> Option<HxReselect> {
+        match self.reswap {
+            Some(HxReswap(SwapStyle {
+                style: Swap::Delete | Swap::None,
+                ..
+            })) => self.reselect.take(),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<HtmxResponseHeaders> for http::HeaderMap {
+    type Error = Vec<HeaderProblem>;
+
+    fn try_from(headers: HtmxResponseHeaders) -> Result<Self, Self::Error> {
+        let mut map = Self::new();
+        headers.try_apply_to(&mut map)?;
+        Ok(map)
+    }
+}
+
+/// a problem with a header in [`HtmxResponseHeaders`], as found by
+/// [`HtmxResponseHeaders::validate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HeaderProblem {
+    header: &'static HeaderName,
+    reason: &'static str,
+}
+
+impl HeaderProblem {
+    const fn new(header: &'static HeaderName, reason: &'static str) -> Self {
+        Self { header, reason }
+    }
+
+    /// the name of the problematic header.
+    #[must_use]
+    pub const fn header(&self) -> &'static HeaderName {
+        self.header
+    }
+
+    /// what is wrong with the header.
+    #[must_use]
+    pub const fn reason(&self) -> &'static str {
+        self.reason
+    }
+}
+
+impl Display for HeaderProblem {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.header, self.reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ScrollModifier, ScrollPosition};
+
+    fn event(name: &str) -> EventName {
+        EventName::new(name).unwrap()
+    }
+
+    fn events<const N: usize>(names: [&str; N]) -> [EventName; N] {
+        names.map(event)
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_decodes_bare_path() {
+        let val = HeaderValue::from_static("/test");
+
+        claims::assert_ok_eq!(
+            HxLocation::decode(&mut std::iter::once(&val)),
+            HxLocation {
+                path: Uri::from_static("/test"),
+                context: None,
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_reports_failed_attempts() {
+        let val = HeaderValue::from_static("http://[::bad");
+
+        let err = claims::assert_err!(HxLocation::try_from(&val));
+        assert_eq!(err.reason(), "not valid JSON and not a valid URI");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_works() {
+        let val = HeaderValue::from_static(r#"{"event2":"Another message", "event1":"A message"}"#);
+
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::decode(&mut std::iter::once(&val)),
+            HxTrigger::with_details(vec![
+                (event("event2"), "Another message".into()),
+                (event("event1"), "A message".into()),
+            ])
+        );
+
+        let val = HeaderValue::from_static("event1, event2");
+
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::decode(&mut std::iter::once(&val)),
+            HxTrigger::list(events(["event1", "event2"]))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn empty_object_decodes_to_empty_details() {
+        let val = HeaderValue::from_static("{}");
+
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::decode(&mut std::iter::once(&val)),
+            HxTrigger::with_details(Vec::new())
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_details_encode_in_insertion_order() {
+        let trigger = HxTrigger::<()>::with_details(vec![
+            (event("zebra"), 1.into()),
+            (event("apple"), 2.into()),
+            (event("mango"), 3.into()),
+        ]);
+
+        let mut values = Vec::new();
+        trigger.encode(&mut values);
+        assert_eq!(
+            values,
+            [HeaderValue::from_static(
+                r#"{"zebra":1,"apple":2,"mango":3}"#
+            )]
+        );
+    }
+
+    #[test]
+    fn trigger_list_allows_namespaced_events() {
+        let trigger = HxTrigger::<()>::list(events(["my.namespace:updated", "htmx:abort"]));
+
+        let mut values = Vec::new();
+        trigger.encode(&mut values);
+        assert_eq!(
+            values,
+            [HeaderValue::from_static("my.namespace:updated, htmx:abort")]
+        );
+        claims::assert_ok_eq!(HxTrigger::<()>::decode(&mut values.iter()), trigger.clone());
+
+        let headers = HtmxResponseHeaders {
+            trigger: Some(trigger),
+            ..HtmxResponseHeaders::default()
+        };
+        claims::assert_ok!(headers.validate());
+    }
+
+    #[test]
+    fn event_name_rejects_list_breaking_names() {
+        for name in [
+            "", " ", "a, b", "a,b", " saved", "saved\t", "a\nb", "a\u{7f}",
+        ] {
+            let err = claims::assert_err!(EventName::new(name), "{name:?}");
+            assert_eq!(err.name(), name);
+        }
+
+        let trigger = HxTrigger::<()>::list(events(["saved", "my event", "htmx:abort"]));
+        let mut values = Vec::new();
+        trigger.encode(&mut values);
+        claims::assert_ok_eq!(HxTrigger::<()>::decode(&mut values.iter()), trigger);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_with_typed_detail() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Saved {
+            id: u32,
+        }
+
+        let trigger =
+            HxTrigger::<AfterSettle>::with_detail(event("itemSaved"), &Saved { id: 7 }).unwrap();
+
+        let mut values = Vec::new();
+        trigger.encode(&mut values);
+        assert_eq!(
+            values,
+            [HeaderValue::from_static(r#"{"itemSaved":{"id":7}}"#)]
+        );
+
+        let decoded = claims::assert_ok!(HxTrigger::<AfterSettle>::decode(&mut values.iter()));
+        claims::assert_some_eq!(
+            decoded
+                .parse_detail::<Saved>("itemSaved")
+                .transpose()
+                .unwrap(),
+            Saved { id: 7 }
+        );
+        claims::assert_none!(decoded.parse_detail::<Saved>("itemDeleted"));
+        claims::assert_err!(decoded.parse_detail::<String>("itemSaved").unwrap());
+
+        let keyed_by_tuple = std::collections::BTreeMap::from([((1, 2), "a")]);
+        claims::assert_err!(HxTrigger::<()>::with_detail(event("bad"), &keyed_by_tuple));
+    }
+
+    #[test]
+    fn trigger_constructors() {
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::single("saved"),
+            HxTrigger::list(events(["saved"]))
+        );
+        claims::assert_ok_eq!(
+            HxTrigger::<AfterSwap>::events(["saved", "notify"]),
+            HxTrigger::list(events(["saved", "notify"]))
+        );
+        claims::assert_ok_eq!(
+            HxTrigger::<AfterSettle>::events(vec!["saved".to_owned()]),
+            HxTrigger::single("saved").unwrap()
+        );
+
+        #[cfg(feature = "serde")]
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::detail("saved", serde_json::json!({ "id": 1 })),
+            HxTrigger::with_details(vec![(event("saved"), serde_json::json!({ "id": 1 }))])
+        );
+    }
+
+    #[test]
+    fn trigger_constructors_reject_invalid_names() {
+        let err = claims::assert_err!(HxTrigger::<()>::single("a, b"));
+        assert_eq!(err.name(), "a, b");
+        claims::assert_err!(HxTrigger::<()>::events(["ok", ""]));
+
+        #[cfg(feature = "serde")]
+        claims::assert_err!(HxTrigger::<()>::detail(" saved", serde_json::Value::Null));
+    }
+
+    #[test]
+    fn display_matches_encode() {
+        #[track_caller]
+        fn assert_display_matches<H: Header + Display>(header: &H) {
+            let mut values = Vec::new();
+            header.encode(&mut values);
+            assert_eq!(
+                values,
+                [HeaderValue::from_str(&header.to_string()).unwrap()]
+            );
+        }
+
+        assert_display_matches(&HxRedirect(Uri::from_static("/login?next=%2F")));
+        assert_display_matches(&HxReswap(
+            "outerHTML swap:1s show:#top:top".parse().unwrap(),
+        ));
+        assert_display_matches(&HxRetarget("closest tr".into()));
+        assert_display_matches(&HxReselect("#content".into()));
+        assert_display_matches(&HxModifyHistory::<HxPushUrl>::uri(Uri::from_static(
+            "/page",
+        )));
+        assert_display_matches(&HxModifyHistory::<HxReplaceUrl>::no_change());
+        assert_display_matches(&HxTrigger::<()>::events(["a", "b"]).unwrap());
+
+        #[cfg(feature = "serde")]
+        {
+            assert_display_matches(&HxLocation::new(Uri::from_static("/home")));
+            assert_display_matches(&HxLocation::with_context(
+                Uri::from_static("/home"),
+                AjaxContext {
+                    target: Some("#main".to_owned()),
+                    ..AjaxContext::default()
+                },
+            ));
+            assert_display_matches(
+                &HxTrigger::<AfterSwap>::detail("saved", serde_json::json!({ "id": 1 })).unwrap(),
+            );
+        }
+
+        assert_eq!(
+            HxModifyHistory::<HxPushUrl>::no_change().to_string(),
+            "false"
+        );
+        assert_eq!(
+            HxTrigger::<()>::events(["a", "b"]).unwrap().to_string(),
+            "a, b"
+        );
+    }
+
+    #[test]
+    fn trigger_rejects_malformed_json() {
+        for value in [r#"{"evt":"#, r#" {"a, b":1}"#, "{evt1, evt2"] {
+            let value = HeaderValue::from_static(value);
+            claims::assert_err!(
+                HxTrigger::<()>::decode(&mut std::iter::once(&value)),
+                "{value:?}"
+            );
+        }
+
+        let value = HeaderValue::from_static("evt1, evt2,evt3");
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::decode(&mut std::iter::once(&value)),
+            HxTrigger::list(events(["evt1", "evt2", "evt3"]))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_decodes_json_string_array() {
+        let value = HeaderValue::from_static(r#"["evt1","evt2"]"#);
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::decode(&mut std::iter::once(&value)),
+            HxTrigger::list(events(["evt1", "evt2"]))
+        );
+
+        for value in [r#"["evt1","ev"#, r#"["a", ""]"#] {
+            let value = HeaderValue::from_static(value);
+            claims::assert_err!(HxTrigger::<()>::decode(&mut std::iter::once(&value)));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_builder_nests_details() {
+        let trigger: HxTrigger = HxTrigger::builder()
+            .event(event("notify"))
+            .detail("level", "info")
+            .detail("ids", vec![1, 2, 3])
+            .build()
+            .unwrap();
+
+        claims::assert_matches!(
+            trigger.triggers(),
+            Triggers::WithDetails(details)
+                if details == &[(
+                    event("notify"),
+                    serde_json::json!({ "level": "info", "ids": [1, 2, 3] })
+                )]
+        );
+
+        let mut values = Vec::new();
+        trigger.encode(&mut values);
+
+        claims::assert_ok_eq!(HxTrigger::<()>::decode(&mut values.iter()), trigger);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_builder_fails_on_unserializable_detail() {
+        let keyed_by_tuple = std::collections::BTreeMap::from([((1, 2), "a")]);
+
+        claims::assert_err!(HxTrigger::<()>::builder()
+            .event(event("first"))
+            .detail("bad", keyed_by_tuple)
+            .event(event("second"))
+            .detail("ok", 1)
+            .build());
+    }
+
+    #[test]
+    fn swap_instruction_from_response() {
+        let mut map = http::HeaderMap::new();
+        map.insert(&HX_RESWAP, HeaderValue::from_static("outerHTML"));
+        map.insert(&HX_RETARGET, HeaderValue::from_static("#main"));
+        map.insert(&HX_RESELECT, HeaderValue::from_static(".content"));
+
+        claims::assert_ok_eq!(
+            SwapInstruction::from_response(&map),
+            SwapInstruction {
+                reswap: Some(HxReswap(Swap::OuterHtml.into())),
+                retarget: Some(HxRetarget("#main".into())),
+                reselect: Some(HxReselect(".content".into())),
+            }
+        );
+    }
+
+    #[test]
+    fn empty_response_headers_apply_nothing() {
+        let mut map = http::HeaderMap::new();
+        HtmxResponseHeaders::default().apply_to(&mut map);
+
+        assert!(map.is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_decodes_ajax_shorthand() {
+        let val = HeaderValue::from_static(
+            r##"{"path":"/x","target":"#y","swap":"innerHTML show:top"}"##,
+        );
+
+        claims::assert_ok_eq!(
+            HxLocation::decode(&mut std::iter::once(&val)),
+            HxLocation {
+                path: Uri::from_static("/x"),
+                context: Some(AjaxContext {
+                    target: Some("#y".to_owned()),
+                    swap: Some(SwapStyle::for_boosted()),
+                    ..AjaxContext::default()
+                }),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_round_trips_typed_swap() {
+        let location = HxLocation {
+            path: Uri::from_static("/x"),
+            context: Some(AjaxContext {
+                swap: Some(Swap::OuterHtml.into()),
+                ..AjaxContext::default()
+            }),
+        };
+
+        let mut values = Vec::new();
+        location.encode(&mut values);
+        assert_eq!(
+            values,
+            [HeaderValue::from_static(
+                r#"{"path":"/x","swap":"outerHTML"}"#
+            )]
+        );
+        claims::assert_ok_eq!(HxLocation::decode(&mut values.iter()), location);
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn selector_headers_share_storage() {
+        let selector = CssSelector::from("#main");
+
+        let mut values = Vec::new();
+        HxRetarget(selector.clone()).encode(&mut values);
+        HxReselect(selector.clone()).encode(&mut values);
+
+        HxRetarget(selector).encode(&mut values);
+
+        assert_eq!(values, ["#main", "#main", "#main"]);
+        for value in &values[1..] {
+            assert!(std::ptr::eq(value.as_bytes(), values[0].as_bytes()));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn try_encode_rejects_invalid_values() {
+        let mut values = Vec::new();
+
+        claims::assert_err!(HxRetarget("#main\n".into()).try_encode(&mut values));
+        claims::assert_err!(HxTrigger::<()>::with_details(vec![(
+            event("a"),
+            serde_json::json!("b\u{7f}")
+        )])
+        .try_encode(&mut values));
+        claims::assert_err!(HxLocation::with_context(
+            Uri::from_static("/x"),
+            AjaxContext {
+                target: Some("#main\u{7f}".to_owned()),
+                ..AjaxContext::default()
+            }
+        )
+        .try_encode(&mut values));
+        assert!(values.is_empty());
+
+        claims::assert_ok!(HxRetarget("#main".into()).try_encode(&mut values));
+        claims::assert_ok!(HxLocation::new(Uri::from_static("/x")).try_encode(&mut values));
+        assert_eq!(
+            values,
+            [
+                HeaderValue::from_static("#main"),
+                HeaderValue::from_static("/x")
+            ]
+        );
+    }
+
+    #[test]
+    fn trigger_dedup_keeps_first_occurrence() {
+        let mut trigger =
+            HxTrigger::<()>::list(events(["saved", "notify", "saved", "refresh", "notify"]));
+
+        assert_eq!(trigger.dedup(), ["saved", "notify"]);
+        assert_eq!(
+            trigger,
+            HxTrigger::list(events(["saved", "notify", "refresh",]))
+        );
+        assert!(trigger.dedup().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_retain_filters_events() {
+        let mut trigger = HxTrigger::<()>::list(events(["saved", "internal", "notify"]));
+        trigger.retain(|event, _| event != "internal");
+
+        assert_eq!(trigger, HxTrigger::list(events(["saved", "notify"])));
+
+        let mut trigger = HxTrigger::<()>::with_details(
+            vec![
+                (event("saved"), serde_json::json!(1)),
+                (event("internal"), serde_json::json!(2)),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        trigger.retain(|_, detail| detail != Some(&serde_json::json!(2)));
+
+        assert_eq!(
+            trigger,
+            HxTrigger::with_details(
+                vec![(event("saved"), serde_json::json!(1))]
+                    .into_iter()
+                    .collect()
+            )
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_from_request_keeps_target() {
+        let headers = RequestHeaders {
+            request: Some(request::HxRequest),
+            target: Some(request::HxTarget("main".to_owned())),
+            ..RequestHeaders::new()
+        };
+
+        assert_eq!(
+            HxLocation::from_request(Uri::from_static("/next"), &headers),
+            HxLocation {
+                path: Uri::from_static("/next"),
+                context: Some(AjaxContext {
+                    target: Some("#main".to_owned()),
+                    ..AjaxContext::default()
+                }),
+            }
+        );
+        assert_eq!(
+            HxLocation::from_request(Uri::from_static("/next"), &RequestHeaders::new()),
+            HxLocation::new(Uri::from_static("/next"))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_from_conversions() {
+        let path = Uri::from_static("/test");
+
+        assert_eq!(
+            HxLocation::new(path.clone()),
+            HxLocation::from(path.clone())
+        );
+        assert_eq!(
+            HxLocation::with_context(
+                path.clone(),
+                AjaxContext {
+                    select: Some("#content".to_owned()),
+                    ..AjaxContext::default()
+                }
+            )
+            .context
+            .and_then(|context| context.select),
+            Some("#content".to_owned())
+        );
+
+        assert_eq!(
+            HxLocation::from(path.clone()),
+            HxLocation {
+                path: path.clone(),
+                context: None,
+            }
+        );
+
+        let context = AjaxContext {
+            target: Some("#main".to_owned()),
+            ..AjaxContext::default()
+        };
+        assert_eq!(
+            HxLocation::from((path.clone(), context.clone())),
+            HxLocation {
+                path: path.clone(),
+                context: Some(context),
+            }
+        );
+
+        assert_eq!(
+            HxLocation::from((path.clone(), AjaxContext::default())),
+            HxLocation {
+                path,
+                context: None,
+            }
+        );
+    }
+
+    #[test]
+    fn reswap_skipped_when_element_default() {
+        let headers = HtmxResponseHeaders::new().reswap(Swap::InnerHtml);
+
+        let mut map = http::HeaderMap::new();
+        headers.apply_to_with(&mut map, &ApplyContext::new().element_swap(Swap::InnerHtml));
+        assert!(map.is_empty());
+
+        headers.apply_to_with(&mut map, &ApplyContext::new().element_swap(Swap::OuterHtml));
+        assert_eq!(
+            map.get(&HX_RESWAP),
+            Some(&HeaderValue::from(Swap::InnerHtml))
+        );
+    }
+
+    #[test]
+    fn modify_history_from_header_value() {
+        claims::assert_ok_eq!(
+            HxModifyHistory::<HxPushUrl>::try_from(&HeaderValue::from_static("false")),
+            HxModifyHistory::no_change()
+        );
+
+        claims::assert_ok_eq!(
+            HxModifyHistory::<HxReplaceUrl>::try_from(&HeaderValue::from_static("/page")),
+            HxModifyHistory::uri(Uri::from_static("/page"))
+        );
+    }
+
+    #[test]
+    fn modify_history_matches_on_value() {
+        let header = HxModifyHistory::<HxPushUrl>::from(Uri::from_static("/page"));
+
+        let HistoryValue::Uri(uri) = header.value() else {
+            panic!("expected a url, got {header:?}");
+        };
+        assert_eq!(uri, "/page");
+        assert_eq!(
+            HxModifyHistory::<HxPushUrl>::no_change().into_value(),
+            HistoryValue::NoChange
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_iter_events() {
+        let trigger = HxTrigger::<()>::list(events(["a", "b"]));
+        assert_eq!(
+            trigger.iter_events().collect::<Vec<_>>(),
+            [("a", None), ("b", None)]
+        );
+
+        let trigger = HxTrigger::<()>::with_details(vec![
+            (event("b"), serde_json::json!(2)),
+            (event("a"), serde_json::json!(1)),
+        ]);
+        assert_eq!(
+            trigger.iter_events().collect::<Vec<_>>(),
+            [
+                ("b", Some(&serde_json::json!(2))),
+                ("a", Some(&serde_json::json!(1)))
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_with_only_target_uses_object_form() {
+        let location = HxLocation {
+            path: Uri::from_static("/test"),
+            context: Some(AjaxContext {
+                target: Some("#main".to_owned()),
+                ..AjaxContext::default()
+            }),
+        };
+
+        let mut values = Vec::new();
+        location.encode(&mut values);
+        assert_eq!(
+            values,
+            [HeaderValue::from_static(
+                r##"{"path":"/test","target":"#main"}"##
+            )]
+        );
+
+        let bare = HxLocation {
+            path: Uri::from_static("/test"),
+            context: Some(AjaxContext::default()),
+        };
+
+        let mut values = Vec::new();
+        bare.encode(&mut values);
+        assert_eq!(values, [HeaderValue::from_static("/test")]);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_default_context_equals_none() {
+        let path = Uri::from_static("/test");
+
+        assert_eq!(
+            HxLocation {
+                path: path.clone(),
+                context: None,
+            },
+            HxLocation {
+                path: path.clone(),
+                context: Some(AjaxContext::default()),
+            }
+        );
+
+        assert_ne!(
+            HxLocation {
+                path: path.clone(),
+                context: None,
+            },
+            HxLocation {
+                path,
+                context: Some(AjaxContext {
+                    target: Some("#main".to_owned()),
+                    ..AjaxContext::default()
+                }),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_acknowledges_prompt() {
+        let prompt = HxPrompt("delete everything".to_owned());
+        let trigger = HxTrigger::<()>::acknowledge_prompt(event("confirmed"), &prompt);
+
+        let mut values = Vec::new();
+        trigger.encode(&mut values);
+        assert_eq!(
+            values,
+            [HeaderValue::from_static(
+                r#"{"confirmed":{"prompt":"delete everything"}}"#
+            )]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn confirmation_accepts_and_rejects() {
+        let mut request = RequestHeaders {
+            prompt: Some(HxPrompt("delete everything".to_owned())),
+            ..RequestHeaders::default()
+        };
+
+        let confirmation = Confirmation::from_request(&request);
+        assert_eq!(confirmation.prompt(), Some("delete everything"));
+        assert_eq!(
+            confirmation.respond::<()>(event("confirmed"), event("cancelled"), |prompt| {
+                prompt == "delete everything"
+            }),
+            HxTrigger::acknowledge_prompt(
+                event("confirmed"),
+                &HxPrompt("delete everything".to_owned())
+            )
+        );
+        assert_eq!(
+            confirmation.respond::<()>(event("confirmed"), event("cancelled"), |_| false),
+            HxTrigger::list(events(["cancelled"]))
+        );
+
+        request.prompt = Some(HxPrompt(String::new()));
+        let confirmation = Confirmation::from_request(&request);
+        assert_eq!(confirmation.prompt(), None);
+        assert_eq!(
+            confirmation.respond::<()>(event("confirmed"), event("cancelled"), |_| unreachable!()),
+            HxTrigger::list(events(["cancelled"]))
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_from_domain_events() {
+        #[derive(Serialize)]
+        #[serde(untagged)]
+        enum DomainEvent {
+            ItemSaved { id: u32 },
+            ItemDeleted { id: u32, permanent: bool },
+        }
+
+        impl HtmxEvent for DomainEvent {
+            fn event_name(&self) -> &str {
+                match self {
+                    Self::ItemSaved { .. } => "itemSaved",
+                    Self::ItemDeleted { .. } => "itemDeleted",
+                }
+            }
+        }
+
+        let trigger = HxTrigger::<()>::from_domain_events([
+            DomainEvent::ItemSaved { id: 1 },
+            DomainEvent::ItemDeleted {
+                id: 2,
+                permanent: true,
+            },
+        ]);
+
+        claims::assert_ok_eq!(
+            trigger,
+            HxTrigger::with_details(
                 vec![
-                    ("event1".to_owned(), "A message".into()),
-                    ("event2".to_owned(), "Another message".into()),
+                    (event("itemSaved"), serde_json::json!({ "id": 1 })),
+                    (
+                        event("itemDeleted"),
+                        serde_json::json!({ "id": 2, "permanent": true })
+                    ),
                 ]
                 .into_iter()
                 .collect()
             )
         );
+    }
 
-        let val = HeaderValue::from_static("event1, event2");
+    #[test]
+    #[cfg(feature = "serde")]
+    fn trigger_from_domain_events_rejects_invalid_events() {
+        struct Event {
+            name: &'static str,
+            serializable: bool,
+        }
+
+        impl HtmxEvent for Event {
+            fn event_name(&self) -> &str {
+                self.name
+            }
+        }
+
+        impl Serialize for Event {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if self.serializable {
+                    serializer.serialize_unit()
+                } else {
+                    Err(serde::ser::Error::custom("unserializable"))
+                }
+            }
+        }
+
+        const fn event(name: &'static str, serializable: bool) -> Event {
+            Event { name, serializable }
+        }
+
+        claims::assert_matches!(
+            HxTrigger::<()>::from_domain_events([event("saved", true), event("a, b", true)]),
+            Err(DomainEventError::InvalidName(err)) if err.name() == "a, b"
+        );
+        claims::assert_matches!(
+            HxTrigger::<()>::from_domain_events([event("saved", false)]),
+            Err(DomainEventError::Serialize(_))
+        );
+    }
+
+    #[test]
+    fn effective_swap_overrides_request() {
+        let mut map = http::HeaderMap::new();
+        map.insert(&HX_RETARGET, HeaderValue::from_static("#errors"));
+        map.insert(&HX_RESWAP, HeaderValue::from_static("beforeend"));
+
+        let swap = EffectiveSwap::from_response(Some("#form"), &map).unwrap();
+        assert_eq!(swap.target(), Some("#errors"));
+        assert_eq!(swap.swap(), Some(&Swap::BeforeEnd.into()));
+        assert_eq!(swap.select(), None);
+    }
+
+    #[test]
+    fn effective_swap_falls_through() {
+        let mut map = http::HeaderMap::new();
+        map.insert(&HX_RESELECT, HeaderValue::from_static(".content"));
+
+        let swap = EffectiveSwap::from_response(Some("#form"), &map).unwrap();
+        assert_eq!(swap.target(), Some("#form"));
+        assert_eq!(swap.swap(), None);
+        assert_eq!(swap.select(), Some(".content"));
+
+        let swap = EffectiveSwap::from_response(None, &http::HeaderMap::new()).unwrap();
+        assert_eq!(swap.target(), None);
+    }
+
+    #[test]
+    fn response_headers_builder_chains() {
+        let map = claims::assert_ok!(http::HeaderMap::try_from(
+            HtmxResponseHeaders::new()
+                .retarget("#x")
+                .reswap(Swap::OuterHtml)
+                .trigger(events(["saved"]))
+                .trigger_after_settle(events(["settled", "done"])),
+        ));
+
+        assert_eq!(map.len(), 4);
+        assert_eq!(map[&HX_RETARGET], "#x");
+        assert_eq!(map[&HX_RESWAP], "outerHTML");
+        assert_eq!(map[&HX_TRIGGER], "saved");
+        assert_eq!(map[&HX_TRIGGER_AFTER_SETTLE], "settled, done");
+    }
+
+    #[test]
+    fn validate_reports_each_problem() {
+        let headers = HtmxResponseHeaders {
+            retarget: Some(HxRetarget("#main\n".into())),
+            reselect: Some(HxReselect("div[data-id".into())),
+            ..HtmxResponseHeaders::default()
+        };
+
+        let problems = claims::assert_err!(headers.validate());
+        assert_eq!(
+            problems
+                .iter()
+                .map(HeaderProblem::header)
+                .collect::<Vec<_>>(),
+            [&HX_RETARGET, &HX_RESELECT]
+        );
+
+        claims::assert_ok!(HtmxResponseHeaders::default().validate());
+
+        let mut map = http::HeaderMap::new();
+        claims::assert_err!(headers.try_apply_to(&mut map));
+        assert!(map.is_empty());
+        claims::assert_err!(http::HeaderMap::try_from(headers));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn validate_reports_unencodable_values() {
+        let headers = HtmxResponseHeaders {
+            trigger: Some(HxTrigger::with_details(vec![(
+                event("a"),
+                serde_json::json!("b\u{7f}"),
+            )])),
+            ..HtmxResponseHeaders::new().location(HxLocation::with_context(
+                Uri::from_static("/x"),
+                AjaxContext {
+                    target: Some("#main\u{7f}".to_owned()),
+                    ..AjaxContext::default()
+                },
+            ))
+        };
+
+        let problems = claims::assert_err!(headers.validate());
+        assert_eq!(
+            problems
+                .iter()
+                .map(HeaderProblem::header)
+                .collect::<Vec<_>>(),
+            [&HX_LOCATION, &HX_TRIGGER]
+        );
+    }
+
+    #[test]
+    fn validate_checks_swap_selectors() {
+        let headers = HtmxResponseHeaders::new().reswap(SwapStyle {
+            show: Some(ScrollModifier {
+                selector: Some("div[data-id".into()),
+                position: ScrollPosition::Top,
+            }),
+            ..SwapStyle::new(Swap::OuterHtml)
+        });
+
+        let problems = claims::assert_err!(headers.validate());
+        assert_eq!(problems[0].header(), &HX_RESWAP);
+        assert_eq!(problems[0].reason(), "selector is malformed");
+
+        let headers = HtmxResponseHeaders::new().reswap(SwapStyle {
+            scroll: Some(ScrollModifier {
+                selector: Some("#list".into()),
+                position: ScrollPosition::Bottom,
+            }),
+            ..SwapStyle::new(Swap::OuterHtml)
+        });
+        claims::assert_ok!(headers.validate());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn validate_checks_location_selectors() {
+        let headers = HtmxResponseHeaders::new().location(HxLocation::with_context(
+            Uri::from_static("/x"),
+            AjaxContext {
+                select: Some("#a > (".to_owned()),
+                ..AjaxContext::default()
+            },
+        ));
+
+        let problems = claims::assert_err!(headers.validate());
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].header(), &HX_LOCATION);
+        assert_eq!(problems[0].reason(), "selector is malformed");
+    }
+
+    #[test]
+    fn apply_to_skips_reselect_for_fragments() {
+        let headers = HtmxResponseHeaders::new().reselect("#content");
+
+        let mut map = http::HeaderMap::new();
+        headers.apply_to(&mut map);
+        assert_eq!(map.get(&HX_RESELECT).unwrap(), "#content");
+
+        let mut map = http::HeaderMap::new();
+        headers.apply_to_with(&mut map, &ApplyContext::new().body_is_fragment(true));
+        claims::assert_none!(map.get(&HX_RESELECT));
+        assert!(HxReselect::needs_reselect(true));
+        assert!(!HxReselect::needs_reselect(false));
+    }
+
+    #[test]
+    fn normalize_drops_reselect_for_delete() {
+        let mut headers = HtmxResponseHeaders {
+            reswap: Some(HxReswap(SwapStyle::new(Swap::Delete))),
+            reselect: Some(HxReselect("#row".into())),
+            ..HtmxResponseHeaders::default()
+        };
+
+        claims::assert_some_eq!(headers.normalize(), HxReselect("#row".into()));
+        claims::assert_none!(&headers.reselect);
+        claims::assert_ok!(headers.validate());
+
+        let mut headers = HtmxResponseHeaders {
+            reswap: Some(HxReswap(SwapStyle::new(Swap::OuterHtml))),
+            reselect: Some(HxReselect("#row".into())),
+            ..HtmxResponseHeaders::default()
+        };
+
+        claims::assert_none!(headers.normalize());
+        claims::assert_some!(&headers.reselect);
+    }
 
+    #[test]
+    #[cfg(feature = "serde")]
+    fn context_from_str() {
         claims::assert_ok_eq!(
-            HxTrigger::<()>::decode(&mut std::iter::once(&val)),
-            HxTrigger::List(vec!["event1".to_owned(), "event2".to_owned()])
+            r##"{"target":"#x"}"##.parse::<AjaxContext>(),
+            AjaxContext {
+                target: Some("#x".to_owned()),
+                ..AjaxContext::default()
+            }
+        );
+
+        let err = claims::assert_err!(AjaxContext::try_from(r##"{"target":"#x""##));
+        assert!(err.is_eof(), "{err}");
+        claims::assert_err!(r#"{"target":1}"#.parse::<AjaxContext>());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn location_decodes_non_string_values() {
+        let val = HeaderValue::from_static(
+            r#"{"path":"/x","values":{"count":3,"draft":true,"tags":["a"],"meta":{"page":2}}}"#,
+        );
+
+        let location = claims::assert_ok!(HxLocation::decode(&mut std::iter::once(&val)));
+
+        assert_eq!(
+            location.context.unwrap().values.unwrap(),
+            [
+                ("count".to_owned(), 3.into()),
+                ("draft".to_owned(), true.into()),
+                ("tags".to_owned(), serde_json::json!(["a"])),
+                ("meta".to_owned(), serde_json::json!({ "page": 2 })),
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn context_maps_serialize_in_order() {
+        let context = AjaxContext {
+            values: Some(vec![("z".to_owned(), 1.into()), ("a".to_owned(), 2.into())]),
+            headers: Some(vec![
+                ("X-Second".to_owned(), "2".to_owned()),
+                ("X-First".to_owned(), "1".to_owned()),
+            ]),
+            ..AjaxContext::default()
+        };
+
+        let location = HxLocation::with_context(Uri::from_static("/x"), context);
+        let json = serde_json::to_string(&location).unwrap();
+        assert_eq!(
+            json,
+            r#"{"path":"/x","values":{"z":1,"a":2},"headers":{"X-Second":"2","X-First":"1"}}"#
+        );
+        assert_eq!(serde_json::from_str::<HxLocation>(&json).unwrap(), location);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn context_set_values_from_struct() {
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Form {
+            name: String,
+            email: String,
+        }
+
+        let form = Form {
+            name: "ferris".to_owned(),
+            email: "ferris@example.com".to_owned(),
+        };
+
+        let mut context = AjaxContext::default();
+        context.set_values(&form).unwrap();
+
+        assert_eq!(
+            context.values.clone().unwrap(),
+            [
+                ("name".to_owned(), "ferris".into()),
+                ("email".to_owned(), "ferris@example.com".into()),
+            ]
+        );
+
+        claims::assert_err!(context.set_values(&"not an object"));
+    }
+
+    #[test]
+    fn trigger_append_to_writes_one_line_per_event() {
+        let trigger = HxTrigger::<()>::list(events(["a", "b"]));
+
+        let mut map = http::HeaderMap::new();
+        trigger.append_to(&mut map);
+
+        assert_eq!(
+            map.get_all(&HX_TRIGGER).iter().collect::<Vec<_>>(),
+            [
+                &HeaderValue::from_static("a"),
+                &HeaderValue::from_static("b")
+            ]
+        );
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::decode(&mut map.get_all(&HX_TRIGGER).iter()),
+            trigger
+        );
+
+        for line in [r#"{"c":null}"#, r#"["c"]"#] {
+            let values = [
+                HeaderValue::from_static("a"),
+                HeaderValue::from_static(line),
+            ];
+            claims::assert_err!(HxTrigger::<()>::decode(&mut values.iter()));
+        }
+    }
+
+    #[test]
+    fn trigger_append_split_to_packs_lines() {
+        let events = (0..20).map(|i| format!("event-{i:02}")).collect::<Vec<_>>();
+        let trigger = HxTrigger::<()>::list(events.iter().map(|name| event(name)));
+
+        let mut map = http::HeaderMap::new();
+        trigger.append_split_to(&mut map, 120);
+
+        let lines = map.get_all(&HX_TRIGGER).iter().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|line| line.len() <= 120));
+        assert_eq!(lines[0], &events[..12].join(", "));
+        claims::assert_ok_eq!(
+            HxTrigger::<()>::decode(&mut map.get_all(&HX_TRIGGER).iter()),
+            trigger
+        );
+    }
+
+    #[test]
+    fn reswap_from_attribute_keeps_modifiers() {
+        let reswap = HxReswap::from_attribute("outerHTML show:window:top").unwrap();
+
+        assert_eq!(
+            reswap,
+            HxReswap(SwapStyle {
+                show: Some(crate::ScrollModifier {
+                    selector: Some("window".into()),
+                    position: crate::ScrollPosition::Top,
+                }),
+                ..SwapStyle::new(Swap::OuterHtml)
+            })
+        );
+
+        let mut values = Vec::new();
+        reswap.encode(&mut values);
+        assert_eq!(
+            values,
+            [HeaderValue::from_static("outerHTML show:window:top")]
         );
     }
+
+    #[test]
+    fn modify_history_dedups_in_hash_set() {
+        let set: std::collections::HashSet<HxModifyHistory<HxPushUrl>> = [
+            HxModifyHistory::uri(Uri::from_static("/page")),
+            HxModifyHistory::uri(Uri::from_static("/page")),
+            HxModifyHistory::no_change(),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(set.len(), 2);
+    }
 }
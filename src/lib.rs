@@ -1,79 +1,1939 @@
 //! Types for working with [htmx](https://htmx.org/).
+//!
+//! the default `serde` feature enables serialization and the JSON-backed
+//! headers: [`HxLocation`](headers::response::HxLocation) and
+//! [`Triggers::WithDetails`](headers::response::Triggers::WithDetails).
+//! without it, `HxTrigger` is list-only.
+//!
+//! the `bytes` feature caches the encoded header value of each
+//! [`CssSelector`], so encoding one into many headers shares a single buffer
+//! instead of copying it each time.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use http::HeaderValue;
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// htmx headers which implement the [`headers_core::Header`] trait.
+///
+/// the [`headers`](https://docs.rs/headers) crate re-exports this trait, so
+/// these types work with its `HeaderMapExt::typed_get` and `typed_insert` as
+/// long as both crates use the same `headers-core` version: `headers` 0.4
+/// uses `headers-core` 0.3, like this crate.
 pub mod headers;
 
 /// The hx-swap attribute allows you to specify how the response will be swapped in relative to the [target](https://htmx.org/attributes/hx-target/) of an AJAX request.
 ///
 /// [htmx docs](https://htmx.org/attributes/hx-swap/)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+///
+/// this enum is `#[non_exhaustive]` so new swap styles can be added without a
+/// breaking change. matches on it need a wildcard arm; use [`Swap::all`] to
+/// enumerate the known styles and [`Swap::as_str`] to get their names instead
+/// of matching exhaustively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[non_exhaustive]
 pub enum Swap {
     /// Replace the inner html of the target element
-    #[serde(rename = "innerHtml")]
+    #[cfg_attr(feature = "serde", serde(rename = "innerHTML", alias = "innerHtml"))]
     InnerHtml,
 
     /// Replace the entire target element with the response
-    #[serde(rename = "outerHtml")]
+    #[cfg_attr(feature = "serde", serde(rename = "outerHTML", alias = "outerHtml"))]
     OuterHtml,
 
+    /// Replace the text content of the target element, without parsing the
+    /// response as html
+    #[cfg_attr(feature = "serde", serde(rename = "textContent"))]
+    TextContent,
+
     /// Insert the response before the target element
-    #[serde(rename = "beforebegin")]
+    #[cfg_attr(feature = "serde", serde(rename = "beforebegin"))]
     BeforeBegin,
 
     /// Insert the response before the first child of the target element
-    #[serde(rename = "afterbegin")]
+    #[cfg_attr(feature = "serde", serde(rename = "afterbegin"))]
     AfterBegin,
 
     /// Insert the response after the last child of the target element
-    #[serde(rename = "beforeend")]
+    #[cfg_attr(feature = "serde", serde(rename = "beforeend"))]
     BeforeEnd,
 
     /// Insert the response after the target element
-    #[serde(rename = "afterend")]
+    #[cfg_attr(feature = "serde", serde(rename = "afterend"))]
     AfterEnd,
 
     /// Deletes the target element regardless of the response
-    #[serde(rename = "delete")]
+    #[cfg_attr(feature = "serde", serde(rename = "delete"))]
     Delete,
 
     /// Does not append content from response (out of band items will still be
     /// processed).
-    #[serde(rename = "none")]
+    #[cfg_attr(feature = "serde", serde(rename = "none"))]
     None,
 }
 
+impl Swap {
+    /// every known swap style.
+    #[must_use]
+    pub const fn all() -> &'static [Self] {
+        &[
+            Self::InnerHtml,
+            Self::OuterHtml,
+            Self::TextContent,
+            Self::BeforeBegin,
+            Self::AfterBegin,
+            Self::BeforeEnd,
+            Self::AfterEnd,
+            Self::Delete,
+            Self::None,
+        ]
+    }
+
+    /// the `hx-swap` value of this swap style.
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::InnerHtml => "innerHTML",
+            Self::OuterHtml => "outerHTML",
+            Self::TextContent => "textContent",
+            Self::BeforeBegin => "beforebegin",
+            Self::AfterBegin => "afterbegin",
+            Self::BeforeEnd => "beforeend",
+            Self::AfterEnd => "afterend",
+            Self::Delete => "delete",
+            Self::None => "none",
+        }
+    }
+
+    /// the `hx-swap` value of this swap style, for embedding in an html
+    /// attribute.
+    ///
+    /// every swap style is plain ascii letters, so the value needs no
+    /// escaping, even in unquoted attributes.
+    #[must_use]
+    pub const fn as_attr_safe(self) -> &'static str {
+        self.as_str()
+    }
+
+    /// whether this is htmx's default swap style, [`Swap::InnerHtml`], used by
+    /// elements without an `hx-swap` attribute.
+    #[must_use]
+    pub const fn is_default(self) -> bool {
+        matches!(self, Self::InnerHtml)
+    }
+
+    /// whether this swap uses the response body.
+    ///
+    /// [`Swap::Delete`] and [`Swap::None`] ignore it, so servers can skip
+    /// rendering one.
+    #[must_use]
+    pub const fn requires_body(self) -> bool {
+        !matches!(self, Self::Delete | Self::None)
+    }
+
+    /// whether this swap inserts content alongside existing content, which
+    /// pairs well with an [`aria-live`](https://developer.mozilla.org/en-US/docs/Web/Accessibility/ARIA/Attributes/aria-live)
+    /// region so assistive technology announces the new content.
+    ///
+    /// this is advisory, intended for template linters.
+    #[must_use]
+    pub const fn suggests_live_region(self) -> bool {
+        matches!(
+            self,
+            Self::BeforeBegin | Self::AfterBegin | Self::BeforeEnd | Self::AfterEnd
+        )
+    }
+
+    /// the DOM operation that reverses this swap, for optimistic ui tooling.
+    ///
+    /// this only describes the operation; it does not perform it.
+    #[must_use]
+    pub const fn undo_hint(self) -> UndoHint {
+        match self {
+            Self::InnerHtml | Self::TextContent => UndoHint::RestoreChildren,
+            Self::OuterHtml => UndoHint::RestoreElement,
+            Self::BeforeBegin => UndoHint::RemovePreviousSiblings,
+            Self::AfterBegin => UndoHint::RemoveFirstChildren,
+            Self::BeforeEnd => UndoHint::RemoveLastChildren,
+            Self::AfterEnd => UndoHint::RemoveNextSiblings,
+            Self::Delete => UndoHint::ReinsertElement,
+            Self::None => UndoHint::Nothing,
+        }
+    }
+
+    /// the shape of response this swap expects, for checking a rendered
+    /// template against it.
+    ///
+    /// [`Swap::OuterHtml`] replaces the target itself, so the response should
+    /// be a single element standing in for it.
+    #[must_use]
+    pub const fn response_shape(self) -> ResponseShape {
+        match self {
+            Self::OuterHtml => ResponseShape::WholeElement,
+            Self::Delete | Self::None => ResponseShape::NoBody,
+            Self::InnerHtml
+            | Self::TextContent
+            | Self::BeforeBegin
+            | Self::AfterBegin
+            | Self::BeforeEnd
+            | Self::AfterEnd => ResponseShape::Fragment,
+        }
+    }
+}
+
+/// the shape of response a [`Swap`] expects, returned by
+/// [`Swap::response_shape`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResponseShape {
+    /// a whole element which replaces the target
+    WholeElement,
+
+    /// any fragment of content, inserted into or around the target
+    Fragment,
+
+    /// no body, since the swap ignores it
+    NoBody,
+}
+
+/// the DOM operation that reverses a [`Swap`], returned by
+/// [`Swap::undo_hint`].
+///
+/// inserted content should be counted when swapping, so the same number of
+/// nodes can be removed later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UndoHint {
+    /// put back the target's previous children
+    RestoreChildren,
+
+    /// replace the swapped-in content with the previous target element
+    RestoreElement,
+
+    /// remove the inserted siblings before the target element
+    RemovePreviousSiblings,
+
+    /// remove the inserted first children of the target element
+    RemoveFirstChildren,
+
+    /// remove the inserted last children of the target element
+    RemoveLastChildren,
+
+    /// remove the inserted siblings after the target element
+    RemoveNextSiblings,
+
+    /// put the deleted target element back in its previous position
+    ReinsertElement,
+
+    /// nothing was swapped, so there is nothing to undo
+    Nothing,
+}
+
 impl From<Swap> for HeaderValue {
     fn from(swap: Swap) -> Self {
-        match swap {
-            Swap::InnerHtml => Self::from_static("innerHtml"),
-            Swap::OuterHtml => Self::from_static("outerHtml"),
-            Swap::BeforeBegin => Self::from_static("beforebegin"),
-            Swap::AfterBegin => Self::from_static("afterbegin"),
-            Swap::BeforeEnd => Self::from_static("beforeend"),
-            Swap::AfterEnd => Self::from_static("afterend"),
-            Swap::Delete => Self::from_static("delete"),
-            Swap::None => Self::from_static("none"),
-        }
+        Self::from_static(swap.as_str())
     }
 }
 
 impl TryFrom<&[u8]> for Swap {
-    type Error = ();
+    type Error = ParseSwapError;
 
     fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
         match bytes {
-            b"innerHtml" => Ok(Self::InnerHtml),
-            b"outerHtml" => Ok(Self::OuterHtml),
+            b"innerHTML" | b"innerHtml" => Ok(Self::InnerHtml),
+            b"outerHTML" | b"outerHtml" => Ok(Self::OuterHtml),
+            b"textContent" => Ok(Self::TextContent),
             b"beforebegin" => Ok(Self::BeforeBegin),
             b"afterbegin" => Ok(Self::AfterBegin),
             b"beforeend" => Ok(Self::BeforeEnd),
             b"afterend" => Ok(Self::AfterEnd),
             b"delete" => Ok(Self::Delete),
             b"none" => Ok(Self::None),
-            _ => Err(()),
+            _ => Err(ParseSwapError {
+                bytes: bytes.to_vec(),
+            }),
+        }
+    }
+}
+
+impl FromStr for Swap {
+    type Err = ParseSwapError;
+
+    /// parses a bare `hx-swap` style, such as `outerHTML`, without modifiers.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.as_bytes().try_into()
+    }
+}
+
+/// an error returned when parsing a [`Swap`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSwapError {
+    bytes: Vec<u8>,
+}
+
+impl ParseSwapError {
+    /// the bytes which could not be parsed.
+    #[must_use]
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+impl Display for ParseSwapError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "invalid swap style: `{}`",
+            String::from_utf8_lossy(&self.bytes)
+        )
+    }
+}
+
+impl std::error::Error for ParseSwapError {}
+
+/// a [`Swap`] along with its modifiers.
+///
+/// [htmx docs](https://htmx.org/attributes/hx-swap/#modifiers)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SwapStyle {
+    /// the base swap style
+    pub style: Swap,
+
+    /// the time to wait after receiving a response before swapping the content
+    pub swap: Option<Duration>,
+
+    /// the time to wait between the swap and the settle step
+    pub settle: Option<Duration>,
+
+    /// scroll an element to its top or bottom after swapping
+    pub scroll: Option<ScrollModifier>,
+
+    /// scroll an element into view after swapping
+    pub show: Option<ScrollModifier>,
+
+    /// whether to scroll a focused element into view after swapping
+    pub focus_scroll: Option<bool>,
+
+    /// whether to use the [view transitions api](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API)
+    /// when swapping
+    pub transition: Option<bool>,
+
+    /// whether to ignore any `<title>` in the response
+    pub ignore_title: Option<bool>,
+}
+
+/// the name used for [`SwapStyle`] where it is paired with its modifiers,
+/// such as in [`HxReswap`](headers::response::HxReswap).
+pub type SwapSpec = SwapStyle;
+
+/// the modifiers of a [`SwapStyle`], without its base [`Swap`].
+///
+/// [htmx docs](https://htmx.org/attributes/hx-swap/#modifiers)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct SwapModifiers {
+    /// the time to wait after receiving a response before swapping the content
+    pub swap: Option<Duration>,
+
+    /// the time to wait between the swap and the settle step
+    pub settle: Option<Duration>,
+
+    /// scroll an element to its top or bottom after swapping
+    pub scroll: Option<ScrollModifier>,
+
+    /// scroll an element into view after swapping
+    pub show: Option<ScrollModifier>,
+
+    /// whether to scroll a focused element into view after swapping
+    pub focus_scroll: Option<bool>,
+
+    /// whether to use the [view transitions api](https://developer.mozilla.org/en-US/docs/Web/API/View_Transitions_API)
+    /// when swapping
+    pub transition: Option<bool>,
+
+    /// whether to ignore any `<title>` in the response
+    pub ignore_title: Option<bool>,
+}
+
+impl SwapModifiers {
+    /// borrow the modifiers for formatting.
+    const fn to_ref(&self) -> ModifiersRef<'_> {
+        ModifiersRef {
+            swap: self.swap,
+            settle: self.settle,
+            scroll: self.scroll.as_ref(),
+            show: self.show.as_ref(),
+            focus_scroll: self.focus_scroll,
+            transition: self.transition,
+            ignore_title: self.ignore_title,
+        }
+    }
+}
+
+impl Display for SwapModifiers {
+    /// formats the modifiers as they appear in an `hx-swap` value, separated
+    /// by single spaces, such as `swap:200ms settle:1s`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.to_ref().fmt(f)
+    }
+}
+
+/// borrowed swap modifiers, shared by the [`Display`] impls of
+/// [`SwapStyle`] and [`SwapModifiers`].
+struct ModifiersRef<'a> {
+    swap: Option<Duration>,
+    settle: Option<Duration>,
+    scroll: Option<&'a ScrollModifier>,
+    show: Option<&'a ScrollModifier>,
+    focus_scroll: Option<bool>,
+    transition: Option<bool>,
+    ignore_title: Option<bool>,
+}
+
+impl ModifiersRef<'_> {
+    const fn is_empty(&self) -> bool {
+        self.swap.is_none()
+            && self.settle.is_none()
+            && self.scroll.is_none()
+            && self.show.is_none()
+            && self.focus_scroll.is_none()
+            && self.transition.is_none()
+            && self.ignore_title.is_none()
+    }
+}
+
+impl Display for ModifiersRef<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut sep = "";
+        let mut next = |f: &mut Formatter<'_>| {
+            let current = sep;
+            sep = " ";
+            f.write_str(current)
+        };
+
+        if let Some(swap) = self.swap {
+            next(f)?;
+            write!(f, "swap:{}", Interval(swap))?;
+        }
+
+        if let Some(settle) = self.settle {
+            next(f)?;
+            write!(f, "settle:{}", Interval(settle))?;
+        }
+
+        if let Some(scroll) = self.scroll {
+            next(f)?;
+            write!(f, "scroll:{scroll}")?;
+        }
+
+        if let Some(show) = self.show {
+            next(f)?;
+            write!(f, "show:{show}")?;
+        }
+
+        if let Some(focus_scroll) = self.focus_scroll {
+            next(f)?;
+            write!(f, "focus-scroll:{focus_scroll}")?;
+        }
+
+        if let Some(transition) = self.transition {
+            next(f)?;
+            write!(f, "transition:{transition}")?;
+        }
+
+        if let Some(ignore_title) = self.ignore_title {
+            next(f)?;
+            write!(f, "ignoreTitle:{ignore_title}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl SwapStyle {
+    /// a swap style with no modifiers.
+    #[must_use]
+    pub const fn new(style: Swap) -> Self {
+        Self {
+            style,
+            swap: None,
+            settle: None,
+            scroll: None,
+            show: None,
+            focus_scroll: None,
+            transition: None,
+            ignore_title: None,
+        }
+    }
+
+    /// a swap style with `modifiers`.
+    #[must_use]
+    pub fn with_modifiers(style: Swap, modifiers: SwapModifiers) -> Self {
+        let SwapModifiers {
+            swap,
+            settle,
+            scroll,
+            show,
+            focus_scroll,
+            transition,
+            ignore_title,
+        } = modifiers;
+
+        Self {
+            style,
+            swap,
+            settle,
+            scroll,
+            show,
+            focus_scroll,
+            transition,
+            ignore_title,
+        }
+    }
+
+    /// the modifiers of this style, without its base [`Swap`].
+    #[must_use]
+    pub fn modifiers(&self) -> SwapModifiers {
+        self.clone().into_parts().1
+    }
+
+    /// split this style into its base [`Swap`] and its modifiers.
+    #[must_use]
+    pub fn into_parts(self) -> (Swap, SwapModifiers) {
+        let Self {
+            style,
+            swap,
+            settle,
+            scroll,
+            show,
+            focus_scroll,
+            transition,
+            ignore_title,
+        } = self;
+
+        (
+            style,
+            SwapModifiers {
+                swap,
+                settle,
+                scroll,
+                show,
+                focus_scroll,
+                transition,
+                ignore_title,
+            },
+        )
+    }
+
+    /// borrow the modifiers for formatting.
+    const fn modifiers_ref(&self) -> ModifiersRef<'_> {
+        ModifiersRef {
+            swap: self.swap,
+            settle: self.settle,
+            scroll: self.scroll.as_ref(),
+            show: self.show.as_ref(),
+            focus_scroll: self.focus_scroll,
+            transition: self.transition,
+            ignore_title: self.ignore_title,
+        }
+    }
+
+    /// the swap style htmx uses for responses to boosted requests:
+    /// [`Swap::InnerHtml`] with `show:top`, so the page starts at the top like
+    /// a normal navigation.
+    ///
+    /// this is a preview aid; htmx applies it on the client.
+    #[must_use]
+    pub const fn for_boosted() -> Self {
+        Self {
+            style: Swap::InnerHtml,
+            swap: None,
+            settle: None,
+            scroll: None,
+            show: Some(ScrollModifier {
+                selector: None,
+                position: ScrollPosition::Top,
+            }),
+            focus_scroll: None,
+            transition: None,
+            ignore_title: None,
+        }
+    }
+
+    /// the swap style htmx uses for responses to boosted forms, the same as
+    /// [`SwapStyle::for_boosted`].
+    ///
+    /// the response replaces the inner html of the `body`, not the form.
+    #[must_use]
+    pub const fn for_boosted_form() -> Self {
+        Self::for_boosted()
+    }
+
+    /// the swap style htmx uses for responses to a boosted link with `href`.
+    ///
+    /// like [`SwapStyle::for_boosted`], except that htmx skips `show:top` when
+    /// `href` contains a `#`, so in-page anchors keep their own scrolling.
+    #[must_use]
+    pub fn for_boosted_link(href: &str) -> Self {
+        if href.contains('#') {
+            Self::new(Swap::InnerHtml)
+        } else {
+            Self::for_boosted()
+        }
+    }
+
+    /// the scrolling which determines where the page ends up after swapping.
+    ///
+    /// htmx restores focus during the swap step, scrolling the focused element
+    /// into view only if `focus-scroll` (or `default_focus_scroll`, mirroring
+    /// htmx's `defaultFocusScroll` config) is `true`. `scroll` and then `show`
+    /// are applied afterwards during the settle step, so `show` takes
+    /// precedence over `scroll`, which takes precedence over focus scrolling. a
+    /// modifier with [`ScrollPosition::None`] does nothing.
+    #[must_use]
+    pub fn effective_scroll(&self, default_focus_scroll: bool) -> ScrollBehavior {
+        let scrolls = |modifier: &Option<ScrollModifier>| {
+            modifier
+                .clone()
+                .filter(|modifier| modifier.position != ScrollPosition::None)
+        };
+
+        match (scrolls(&self.show), scrolls(&self.scroll)) {
+            (Some(show), _) => ScrollBehavior::Show(show),
+            (None, Some(scroll)) => ScrollBehavior::Scroll(scroll),
+            (None, None) if self.focus_scroll.unwrap_or(default_focus_scroll) => {
+                ScrollBehavior::FocusedElement
+            }
+            (None, None) => ScrollBehavior::None,
+        }
+    }
+
+    /// fills in any unset `swap`/`settle` durations from `defaults`, mirroring
+    /// how htmx falls back to its global config.
+    #[must_use]
+    pub fn resolve_timings(&self, defaults: SwapTimingDefaults) -> ResolvedTimings {
+        ResolvedTimings {
+            swap: self.swap.unwrap_or(defaults.swap),
+            settle: self.settle.unwrap_or(defaults.settle),
+        }
+    }
+
+    /// whether both styles share the same base [`Swap`], ignoring modifiers.
+    #[must_use]
+    pub fn same_base(&self, other: &Self) -> bool {
+        self.style == other.style
+    }
+
+    /// parses a swap style like [`FromStr`], but first resolves the base swap
+    /// through `aliases`.
+    ///
+    /// names which are not aliases are parsed as usual.
+    pub fn from_str_with_aliases(
+        s: &str,
+        aliases: &SwapAliases,
+    ) -> Result<Self, ParseSwapStyleError> {
+        Self::parse(s, false, |base| {
+            aliases
+                .0
+                .get(base)
+                .copied()
+                .or_else(|| base.as_bytes().try_into().ok())
+        })
+    }
+
+    /// parses a swap style like [`FromStr`], but fails on any unrecognized
+    /// modifier instead of ignoring it.
+    ///
+    /// the returned error names the offending token.
+    pub fn from_str_strict(s: &str) -> Result<Self, ParseSwapStyleError> {
+        Self::parse(s, true, |base| base.as_bytes().try_into().ok())
+    }
+
+    fn parse(
+        s: &str,
+        strict: bool,
+        base: impl FnOnce(&str) -> Option<Swap>,
+    ) -> Result<Self, ParseSwapStyleError> {
+        let mut tokens = s.split_whitespace();
+
+        let first = tokens.next().unwrap_or_default();
+        let mut style = Self::new(base(first).ok_or_else(|| ParseSwapStyleError::new(first))?);
+
+        for token in tokens {
+            let invalid = || ParseSwapStyleError::new(token);
+
+            match token.split_once(':') {
+                Some(("swap", value)) => {
+                    style.swap = Some(parse_interval(value).ok_or_else(invalid)?);
+                }
+                Some(("settle", value)) => {
+                    style.settle = Some(parse_interval(value).ok_or_else(invalid)?);
+                }
+                Some(("scroll", value)) => {
+                    style.scroll = Some(value.parse().map_err(|()| invalid())?);
+                }
+                Some(("show", value)) => {
+                    style.show = Some(value.parse().map_err(|()| invalid())?);
+                }
+                Some(("focus-scroll", value)) => {
+                    style.focus_scroll = Some(value.parse().map_err(|_| invalid())?);
+                }
+                Some(("transition", value)) => {
+                    style.transition = Some(value.parse().map_err(|_| invalid())?);
+                }
+                Some(("ignoreTitle", value)) => {
+                    style.ignore_title = Some(value.parse().map_err(|_| invalid())?);
+                }
+                _ if strict => return Err(ParseSwapStyleError::new(token)),
+                _ => {}
+            }
+        }
+
+        Ok(style)
+    }
+}
+
+impl TryFrom<&SwapStyle> for HeaderValue {
+    type Error = http::header::InvalidHeaderValue;
+
+    /// encodes the style as an `hx-swap` value, using [`Display`].
+    ///
+    /// fails if a `scroll` or `show` selector is not header-safe. styles
+    /// without modifiers can use the infallible `From<Swap>` instead.
+    fn try_from(style: &SwapStyle) -> Result<Self, Self::Error> {
+        Self::from_str(&style.to_string())
+    }
+}
+
+impl From<Swap> for SwapStyle {
+    fn from(style: Swap) -> Self {
+        Self::new(style)
+    }
+}
+
+impl From<(Swap, SwapModifiers)> for SwapStyle {
+    fn from((style, modifiers): (Swap, SwapModifiers)) -> Self {
+        Self::with_modifiers(style, modifiers)
+    }
+}
+
+impl Display for SwapStyle {
+    /// formats the style as an `hx-swap` value, with modifiers separated by
+    /// single spaces.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.style.as_str())?;
+
+        let modifiers = self.modifiers_ref();
+        if modifiers.is_empty() {
+            Ok(())
+        } else {
+            write!(f, " {modifiers}")
+        }
+    }
+}
+
+impl FromStr for SwapStyle {
+    type Err = ParseSwapStyleError;
+
+    /// parses an `hx-swap` value, such as `innerHTML swap:200ms settle:1s`.
+    ///
+    /// tokens may be separated by any run of whitespace, like htmx allows.
+    /// unknown modifiers are ignored.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, false, |base| base.as_bytes().try_into().ok())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SwapStyle {
+    /// serializes the style as an `hx-swap` string, using [`Display`].
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SwapStyle {
+    /// deserializes the style from either an `hx-swap` string, using
+    /// [`FromStr`], or an object like
+    /// `{"style": "outerHTML", "modifiers": "swap:1s"}`, where `modifiers` is
+    /// optional.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            String(String),
+            Object {
+                style: Swap,
+                #[serde(default)]
+                modifiers: String,
+            },
         }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::String(s) => s.parse().map_err(serde::de::Error::custom),
+            Repr::Object { style, modifiers } => format!("{} {modifiers}", style.as_str())
+                .parse()
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// a `scroll` or `show` modifier of a [`SwapStyle`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ScrollModifier {
+    /// the element to scroll, or `None` for the target element
+    ///
+    /// `show` also accepts `window` here.
+    pub selector: Option<CssSelector>,
+
+    /// where to scroll to
+    pub position: ScrollPosition,
+}
+
+impl FromStr for ScrollModifier {
+    type Err = ();
+
+    /// parses a modifier value, such as `top` or `#el:bottom`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (selector, position) = match s.rsplit_once(':') {
+            Some((selector, position)) => (Some(selector.into()), position),
+            None => (None, s),
+        };
+
+        let position = match position {
+            "top" => ScrollPosition::Top,
+            "bottom" => ScrollPosition::Bottom,
+            "none" => ScrollPosition::None,
+            _ => return Err(()),
+        };
+
+        Ok(Self { selector, position })
+    }
+}
+
+impl Display for ScrollModifier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(selector) = &self.selector {
+            write!(f, "{selector}:")?;
+        }
+
+        f.write_str(match self.position {
+            ScrollPosition::Top => "top",
+            ScrollPosition::Bottom => "bottom",
+            ScrollPosition::None => "none",
+        })
+    }
+}
+
+/// a position to scroll to, for a [`ScrollModifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ScrollPosition {
+    /// the top of the element
+    Top,
+
+    /// the bottom of the element
+    Bottom,
+
+    /// do not scroll, overriding any default such as the `show:top` used for
+    /// boosted requests
+    None,
+}
+
+/// the scrolling which takes effect after a swap, as returned by
+/// [`SwapStyle::effective_scroll`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ScrollBehavior {
+    /// an element is scrolled into view by the `show` modifier
+    Show(ScrollModifier),
+
+    /// an element is scrolled by the `scroll` modifier
+    Scroll(ScrollModifier),
+
+    /// the focused element, if any, is scrolled into view
+    FocusedElement,
+
+    /// nothing is scrolled
+    None,
+}
+
+/// an error returned when parsing a [`SwapStyle`] fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseSwapStyleError {
+    token: String,
+}
+
+impl ParseSwapStyleError {
+    fn new(token: &str) -> Self {
+        Self {
+            token: token.to_owned(),
+        }
+    }
+
+    /// the token which could not be parsed.
+    #[must_use]
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+impl Display for ParseSwapStyleError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid swap style token: `{}`", self.token)
+    }
+}
+
+impl std::error::Error for ParseSwapStyleError {}
+
+/// formats a duration as an htmx time interval, in whole seconds if possible.
+///
+/// durations which are not a whole number of milliseconds are written
+/// exactly, as fractional milliseconds such as `1.5ms`.
+struct Interval(Duration);
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let sub_ms_nanos = self.0.subsec_nanos() % 1_000_000;
+
+        if self.0.subsec_nanos() == 0 && !self.0.is_zero() {
+            write!(f, "{}s", self.0.as_secs())
+        } else if sub_ms_nanos == 0 {
+            write!(f, "{}ms", self.0.as_millis())
+        } else {
+            let fraction = format!("{sub_ms_nanos:06}");
+            write!(
+                f,
+                "{}.{}ms",
+                self.0.as_millis(),
+                fraction.trim_end_matches('0')
+            )
+        }
+    }
+}
+
+/// parses an htmx time interval, such as `200ms`, `1s`, or `200`
+/// (milliseconds).
+fn parse_interval(s: &str) -> Option<Duration> {
+    let (n, scale) = s
+        .strip_suffix("ms")
+        .map(|ms| (ms, 1e-3))
+        .or_else(|| s.strip_suffix('s').map(|secs| (secs, 1.0)))
+        .unwrap_or((s, 1e-3));
+
+    let n = n.parse::<f64>().ok()?;
+    Duration::try_from_secs_f64(n * scale).ok()
+}
+
+/// team-specific names for swap styles, for use with
+/// [`SwapStyle::from_str_with_aliases`].
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SwapAliases(HashMap<String, Swap>);
+
+impl SwapAliases {
+    /// create an empty set of aliases.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// register `alias` as another name for `swap`.
+    pub fn insert(&mut self, alias: impl Into<String>, swap: Swap) -> &mut Self {
+        self.0.insert(alias.into(), swap);
+        self
+    }
+}
+
+/// htmx's default `defaultSwapDelay`, used when a [`SwapStyle`] has no
+/// `swap` modifier.
+///
+/// [htmx docs](https://htmx.org/docs/#config)
+pub const DEFAULT_SWAP_DELAY: Duration = Duration::ZERO;
+
+/// htmx's default `defaultSettleDelay`, used when a [`SwapStyle`] has no
+/// `settle` modifier.
+///
+/// [htmx docs](https://htmx.org/docs/#config)
+pub const DEFAULT_SETTLE_DELAY: Duration = Duration::from_millis(20);
+
+/// default timings to use when a [`SwapStyle`] does not specify them.
+///
+/// these mirror htmx's `defaultSwapDelay` and `defaultSettleDelay` config
+/// options, and default to [`DEFAULT_SWAP_DELAY`] and
+/// [`DEFAULT_SETTLE_DELAY`].
+///
+/// [htmx docs](https://htmx.org/docs/#config)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SwapTimingDefaults {
+    /// the default swap delay
+    pub swap: Duration,
+
+    /// the default settle delay
+    pub settle: Duration,
+}
+
+impl Default for SwapTimingDefaults {
+    fn default() -> Self {
+        Self {
+            swap: DEFAULT_SWAP_DELAY,
+            settle: DEFAULT_SETTLE_DELAY,
+        }
+    }
+}
+
+/// the effective timings of a [`SwapStyle`], as returned by
+/// [`SwapStyle::resolve_timings`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ResolvedTimings {
+    /// the time to wait before swapping the content
+    pub swap: Duration,
+
+    /// the time to wait between the swap and the settle step
+    pub settle: Duration,
+}
+
+/// a CSS selector, such as one used to retarget or reselect a response.
+///
+/// ordering, [`Display`], and serde all use the raw selector string. the string
+/// is reference counted, so cloning a selector to reuse it across many headers
+/// does not copy it.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "Arc<str>", into = "Arc<str>")
+)]
+pub struct CssSelector {
+    selector: Arc<str>,
+
+    /// the encoded header value, shared by every clone of the selector.
+    #[cfg(feature = "bytes")]
+    header: HeaderCache,
+}
+
+/// a selector's header value, encoded once into a shared buffer when the
+/// selector is created, or `None` if it is not a valid header value.
+///
+/// it is ignored by comparisons and hashing, since it is derived from the
+/// selector.
+#[cfg(feature = "bytes")]
+#[derive(Debug, Clone)]
+struct HeaderCache(Option<HeaderValue>);
+
+#[cfg(feature = "bytes")]
+impl HeaderCache {
+    fn new(selector: &str) -> Self {
+        Self(
+            HeaderValue::from_maybe_shared(bytes::Bytes::copy_from_slice(selector.as_bytes())).ok(),
+        )
+    }
+
+    /// the cached header value, or the error from encoding `selector`.
+    fn get(&self, selector: &str) -> Result<HeaderValue, http::header::InvalidHeaderValue> {
+        self.0
+            .clone()
+            .map_or_else(|| HeaderValue::from_str(selector), Ok)
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl PartialEq for HeaderCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Eq for HeaderCache {}
+
+#[cfg(feature = "bytes")]
+impl PartialOrd for HeaderCache {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl Ord for HeaderCache {
+    fn cmp(&self, _: &Self) -> std::cmp::Ordering {
+        std::cmp::Ordering::Equal
+    }
+}
+
+#[cfg(feature = "bytes")]
+impl std::hash::Hash for HeaderCache {
+    fn hash<H: std::hash::Hasher>(&self, _: &mut H) {}
+}
+
+impl CssSelector {
+    /// create a selector, checking that it is non-empty and that its brackets
+    /// and quotes are balanced.
+    ///
+    /// the htmx extended forms `this`, `closest <selector>`,
+    /// `find <selector>`, `next [selector]`, and `previous [selector]` are
+    /// accepted. the [`From`] impls skip these checks.
+    pub fn new(selector: &str) -> Result<Self, InvalidCssSelector> {
+        let trimmed = selector.trim();
+        let rest = match trimmed.split_once(char::is_whitespace) {
+            _ if matches!(trimmed, "this" | "next" | "previous") => {
+                return Ok(Self::shared(selector));
+            }
+            _ if matches!(trimmed, "closest" | "find") => "",
+            Some(("closest" | "find" | "next" | "previous", rest)) => rest.trim_start(),
+            _ => trimmed,
+        };
+
+        if rest.is_empty() || !balanced(rest) {
+            return Err(InvalidCssSelector {
+                selector: selector.to_owned(),
+            });
+        }
+
+        Ok(Self::shared(selector))
+    }
+
+    /// the raw selector string.
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.selector
+    }
+
+    fn shared(selector: impl Into<Arc<str>>) -> Self {
+        let selector = selector.into();
+
+        Self {
+            #[cfg(feature = "bytes")]
+            header: HeaderCache::new(&selector),
+            selector,
+        }
+    }
+}
+
+impl fmt::Debug for CssSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CssSelector").field(&self.selector).finish()
+    }
+}
+
+/// whether the brackets and quotes in `selector` are balanced.
+fn balanced(selector: &str) -> bool {
+    let mut open = Vec::new();
+    let mut quote = None;
+    let mut chars = selector.chars();
+
+    while let Some(c) = chars.next() {
+        match (quote, c) {
+            (_, '\\') => {
+                chars.next();
+            }
+            (Some(q), c) if c == q => quote = None,
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => open.push(')'),
+            (None, '[') => open.push(']'),
+            (None, ')' | ']') if open.pop() != Some(c) => return false,
+            _ => {}
+        }
+    }
+
+    open.is_empty() && quote.is_none()
+}
+
+impl TryFrom<&[u8]> for CssSelector {
+    type Error = std::str::Utf8Error;
+
+    /// decodes any UTF-8 selector, without the checks of [`CssSelector::new`].
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        std::str::from_utf8(bytes).map(Self::from)
+    }
+}
+
+impl From<String> for CssSelector {
+    fn from(selector: String) -> Self {
+        Self::shared(selector)
+    }
+}
+
+impl From<&str> for CssSelector {
+    fn from(selector: &str) -> Self {
+        Self::shared(selector)
+    }
+}
+
+impl From<Arc<str>> for CssSelector {
+    fn from(selector: Arc<str>) -> Self {
+        Self::shared(selector)
+    }
+}
+
+impl From<CssSelector> for Arc<str> {
+    fn from(selector: CssSelector) -> Self {
+        selector.selector
+    }
+}
+
+impl Display for CssSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.selector)
+    }
+}
+
+impl TryFrom<&CssSelector> for HeaderValue {
+    type Error = http::header::InvalidHeaderValue;
+
+    /// encodes the raw selector.
+    ///
+    /// with the `bytes` feature, the value is encoded once when the selector is
+    /// created and shared by every clone of it, instead of being copied each
+    /// time.
+    fn try_from(selector: &CssSelector) -> Result<Self, Self::Error> {
+        #[cfg(feature = "bytes")]
+        let value = selector.header.get(selector.as_str());
+        #[cfg(not(feature = "bytes"))]
+        let value = Self::from_str(selector.as_str());
+
+        value
+    }
+}
+
+/// an error returned when [`CssSelector::new`] rejects a selector.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InvalidCssSelector {
+    selector: String,
+}
+
+impl InvalidCssSelector {
+    /// the selector which was rejected.
+    #[must_use]
+    pub fn selector(&self) -> &str {
+        &self.selector
+    }
+}
+
+impl Display for InvalidCssSelector {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid css selector: `{}`", self.selector)
+    }
+}
+
+impl std::error::Error for InvalidCssSelector {}
+
+/// the value of an `hx-swap-oob` attribute, for rendering out of band swaps.
+///
+/// [htmx docs](https://htmx.org/attributes/hx-swap-oob/)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum SwapOob {
+    /// swap the element with a matching `id` using [`Swap::OuterHtml`]
+    True,
+
+    /// swap the element with a matching `id` using the given style
+    Swap(Swap),
+
+    /// swap the elements matching a selector using the given style
+    ///
+    /// htmx splits this form on its first `:`, so only the base [`Swap`] can
+    /// be given, without modifiers.
+    Selector(Swap, CssSelector),
+}
+
+impl SwapOob {
+    /// the full attribute for swapping into `target` with `style`, such as
+    /// `hx-swap-oob="innerHTML:#id"`.
+    #[must_use]
+    pub fn attribute_for(style: Swap, target: &CssSelector) -> String {
+        Self::Selector(style, target.clone()).attribute()
+    }
+
+    /// the full attribute, such as `hx-swap-oob="true"`.
+    ///
+    /// quotes and ampersands in a selector are escaped.
+    #[must_use]
+    pub fn attribute(&self) -> String {
+        let value = match self {
+            Self::True => "true".to_owned(),
+            Self::Swap(swap) => swap.as_str().to_owned(),
+            Self::Selector(swap, selector) => format!(
+                "{}:{}",
+                swap.as_str(),
+                selector
+                    .as_str()
+                    .replace('&', "&amp;")
+                    .replace('"', "&quot;")
+            ),
+        };
+
+        format!(r#"hx-swap-oob="{value}""#)
+    }
+}
+
+impl Display for SwapOob {
+    /// formats the raw attribute value, such as `beforeend:#messages`, without
+    /// escaping.
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::True => f.write_str("true"),
+            Self::Swap(swap) => f.write_str(swap.as_str()),
+            Self::Selector(swap, selector) => write!(f, "{}:{selector}", swap.as_str()),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SwapOob {
+    /// serializes the attribute value as a string, using [`Display`].
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SwapOob {
+    /// deserializes the attribute value from either the boolean `true` or a
+    /// string, using [`FromStr`].
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Bool(bool),
+            String(String),
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Bool(true) => Ok(Self::True),
+            Repr::Bool(false) => Err(serde::de::Error::custom(
+                "`false` is not a valid out of band swap",
+            )),
+            Repr::String(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+impl FromStr for SwapOob {
+    type Err = ParseSwapError;
+
+    /// parses an `hx-swap-oob` value: `true`, a bare style, or a style and a
+    /// selector separated by the first `:`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseSwapError {
+            bytes: s.as_bytes().to_vec(),
+        };
+
+        match s.split_once(':') {
+            _ if s == "true" => Ok(Self::True),
+            None => s.parse().map(Self::Swap).map_err(|_| invalid()),
+            Some((_, "")) => Err(invalid()),
+            Some((swap, selector)) => swap
+                .parse()
+                .map(|swap| Self::Selector(swap, selector.into()))
+                .map_err(|_| invalid()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn css_selector_new_validates() {
+        for selector in [
+            "#main",
+            "this",
+            "next",
+            "closest tr",
+            "find .item:not([data-skip])",
+            "previous  div",
+            r#"a[href="(unbalanced"]"#,
+        ] {
+            claims::assert_ok!(CssSelector::new(selector), "{selector}");
+        }
+
+        for selector in ["", "   ", "closest ", "find", "div[data-id", "a)", "[x=']"] {
+            let err = claims::assert_err!(CssSelector::new(selector), "{selector}");
+            assert_eq!(err.selector(), selector);
+        }
+    }
+
+    #[test]
+    fn default_timings_match_htmx() {
+        assert_eq!(DEFAULT_SWAP_DELAY, Duration::from_millis(0));
+        assert_eq!(DEFAULT_SETTLE_DELAY, Duration::from_millis(20));
+        assert_eq!(
+            SwapStyle::new(Swap::InnerHtml).resolve_timings(SwapTimingDefaults::default()),
+            ResolvedTimings {
+                swap: DEFAULT_SWAP_DELAY,
+                settle: DEFAULT_SETTLE_DELAY,
+            }
+        );
+    }
+
+    #[test]
+    fn swap_oob_parses_each_form() {
+        for (value, oob) in [
+            ("true", SwapOob::True),
+            ("innerHTML", SwapOob::Swap(Swap::InnerHtml)),
+            (
+                "beforeend:#messages",
+                SwapOob::Selector(Swap::BeforeEnd, "#messages".into()),
+            ),
+            (
+                "outerHTML:a[href=\"a:b\"]",
+                SwapOob::Selector(Swap::OuterHtml, "a[href=\"a:b\"]".into()),
+            ),
+        ] {
+            assert_eq!(claims::assert_ok!(value.parse::<SwapOob>()), oob);
+            assert_eq!(oob.to_string(), value);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn swap_oob_serde() {
+        claims::assert_ok_eq!(serde_json::from_str::<SwapOob>("true"), SwapOob::True);
+        claims::assert_ok_eq!(serde_json::from_str::<SwapOob>(r#""true""#), SwapOob::True);
+        claims::assert_ok_eq!(
+            serde_json::from_str::<SwapOob>(r#""beforeend:#messages""#),
+            SwapOob::Selector(Swap::BeforeEnd, "#messages".into())
+        );
+        claims::assert_err!(serde_json::from_str::<SwapOob>("false"));
+        claims::assert_err!(serde_json::from_str::<SwapOob>(r#""sideways""#));
+
+        claims::assert_ok_eq!(serde_json::to_string(&SwapOob::True), r#""true""#);
+        claims::assert_ok_eq!(
+            serde_json::to_string(&SwapOob::Swap(Swap::OuterHtml)),
+            r#""outerHTML""#
+        );
+    }
+
+    #[test]
+    fn swap_oob_rejects_malformed() {
+        for value in ["", "false", "sideways", "beforeend:", "true:#messages"] {
+            let err = claims::assert_err!(value.parse::<SwapOob>());
+            assert_eq!(err.bytes(), value.as_bytes());
+        }
+    }
+
+    #[test]
+    fn interval_keeps_sub_millisecond_durations() {
+        for (duration, text) in [
+            (Duration::from_micros(1500), "1.5ms"),
+            (Duration::from_nanos(1), "0.000001ms"),
+            (Duration::from_micros(2_000_250), "2000.25ms"),
+            (Duration::from_millis(1500), "1500ms"),
+            (Duration::from_secs(2), "2s"),
+        ] {
+            assert_eq!(Interval(duration).to_string(), text);
+
+            let style = SwapStyle {
+                swap: Some(duration),
+                ..SwapStyle::new(Swap::InnerHtml)
+            };
+            claims::assert_ok_eq!(style.to_string().parse::<SwapStyle>(), style);
+        }
+    }
+
+    #[test]
+    fn resolve_timings_inherits_unset() {
+        let style = SwapStyle {
+            swap: Some(Duration::from_millis(200)),
+            ..SwapStyle::new(Swap::InnerHtml)
+        };
+
+        let defaults = SwapTimingDefaults::default();
+
+        assert_eq!(
+            style.resolve_timings(defaults),
+            ResolvedTimings {
+                swap: Duration::from_millis(200),
+                settle: Duration::from_millis(20),
+            }
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn css_selectors_sort_by_raw_string() {
+        let selectors: std::collections::BTreeSet<CssSelector> =
+            ["#main", ".item", "#aside", "closest div"]
+                .into_iter()
+                .map(CssSelector::from)
+                .collect();
+
+        assert_eq!(
+            selectors
+                .iter()
+                .map(CssSelector::as_str)
+                .collect::<Vec<_>>(),
+            ["#aside", "#main", ".item", "closest div"]
+        );
+
+        assert_eq!(CssSelector::from("#main").to_string(), "#main");
+        assert_eq!(
+            serde_json::to_string(&CssSelector::from("#main")).unwrap(),
+            r##""#main""##
+        );
+    }
+
+    #[test]
+    fn swap_style_resolves_aliases() {
+        let mut aliases = SwapAliases::new();
+        aliases.insert("fill", Swap::InnerHtml);
+
+        assert_eq!(
+            SwapStyle::from_str_with_aliases("fill settle:1s", &aliases),
+            Ok(SwapStyle {
+                settle: Some(Duration::from_secs(1)),
+                ..SwapStyle::new(Swap::InnerHtml)
+            })
+        );
+
+        assert_eq!(
+            SwapStyle::from_str_with_aliases("outerHTML", &aliases),
+            Ok(SwapStyle::new(Swap::OuterHtml))
+        );
+
+        assert_eq!(
+            "fill".parse::<SwapStyle>(),
+            Err(ParseSwapStyleError::new("fill"))
+        );
+    }
+
+    #[test]
+    fn inner_html_is_default() {
+        assert!(Swap::InnerHtml.is_default());
+        assert!(!Swap::OuterHtml.is_default());
+    }
+
+    #[test]
+    fn delete_and_none_require_no_body() {
+        let cases = [
+            (Swap::InnerHtml, true),
+            (Swap::OuterHtml, true),
+            (Swap::TextContent, true),
+            (Swap::BeforeBegin, true),
+            (Swap::AfterBegin, true),
+            (Swap::BeforeEnd, true),
+            (Swap::AfterEnd, true),
+            (Swap::Delete, false),
+            (Swap::None, false),
+        ];
+
+        for (swap, expected) in cases {
+            assert_eq!(swap.requires_body(), expected, "{swap:?}");
+        }
+    }
+
+    #[test]
+    fn insert_swaps_suggest_live_region() {
+        let cases = [
+            (Swap::InnerHtml, false),
+            (Swap::OuterHtml, false),
+            (Swap::TextContent, false),
+            (Swap::BeforeBegin, true),
+            (Swap::AfterBegin, true),
+            (Swap::BeforeEnd, true),
+            (Swap::AfterEnd, true),
+            (Swap::Delete, false),
+            (Swap::None, false),
+        ];
+
+        for (swap, expected) in cases {
+            assert_eq!(swap.suggests_live_region(), expected, "{swap:?}");
+        }
+    }
+
+    #[test]
+    fn same_base_ignores_modifiers() {
+        let a = SwapStyle {
+            swap: Some(Duration::from_millis(100)),
+            ..SwapStyle::new(Swap::OuterHtml)
+        };
+        let b = SwapStyle {
+            settle: Some(Duration::from_secs(1)),
+            ..SwapStyle::new(Swap::OuterHtml)
+        };
+
+        assert!(a.same_base(&b));
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn swap_oob_attributes() {
+        assert_eq!(SwapOob::True.attribute(), r#"hx-swap-oob="true""#);
+
+        assert_eq!(
+            SwapOob::attribute_for(Swap::BeforeEnd, &"#messages".into()),
+            r#"hx-swap-oob="beforeend:#messages""#
+        );
+    }
+
+    #[test]
+    fn css_selector_clones_share_storage() {
+        let selector = CssSelector::from("#main");
+        let oob = [
+            SwapOob::Selector(Swap::InnerHtml, selector.clone()),
+            SwapOob::Selector(Swap::BeforeEnd, selector.clone()),
+        ];
+
+        for oob in &oob {
+            let SwapOob::Selector(_, shared) = oob else {
+                unreachable!()
+            };
+            assert!(std::ptr::eq(shared.as_str(), selector.as_str()));
+        }
+    }
+
+    #[test]
+    fn strict_parsing_rejects_unknown_modifiers() {
+        let lenient = SwapStyle::new(Swap::OuterHtml);
+
+        assert_eq!("outerHTML scrol:top".parse(), Ok(lenient.clone()));
+        assert_eq!(
+            SwapStyle::from_str_strict("outerHTML scrol:top"),
+            Err(ParseSwapStyleError::new("scrol:top"))
+        );
+        assert_eq!(
+            SwapStyle::from_str_strict("outerHTML transition:true"),
+            Ok(SwapStyle {
+                transition: Some(true),
+                ..lenient
+            })
+        );
+    }
+
+    #[test]
+    fn swap_modifiers_split_and_join() {
+        let modifiers = SwapModifiers {
+            swap: Some(Duration::from_millis(200)),
+            settle: Some(Duration::from_secs(1)),
+            ..SwapModifiers::default()
+        };
+        assert_eq!(modifiers.to_string(), "swap:200ms settle:1s");
+        assert_eq!(SwapModifiers::default().to_string(), "");
+
+        let spec: SwapSpec = (Swap::InnerHtml, modifiers.clone()).into();
+        assert_eq!(spec.to_string(), "innerHTML swap:200ms settle:1s");
+        assert_eq!(spec.modifiers(), modifiers);
+        assert_eq!(spec.clone().into_parts(), (Swap::InnerHtml, modifiers));
+        assert_eq!(
+            HeaderValue::try_from(&spec).unwrap(),
+            "innerHTML swap:200ms settle:1s"
+        );
+        assert_eq!(SwapSpec::new(Swap::OuterHtml).to_string(), "outerHTML");
+    }
+
+    #[test]
+    fn swap_style_transition_and_ignore_title() {
+        let style: SwapStyle = "innerHTML ignoreTitle:true swap:200ms transition:false settle:1s"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            style,
+            SwapStyle {
+                swap: Some(Duration::from_millis(200)),
+                settle: Some(Duration::from_secs(1)),
+                transition: Some(false),
+                ignore_title: Some(true),
+                ..SwapStyle::new(Swap::InnerHtml)
+            }
+        );
+        assert_eq!(
+            style.to_string(),
+            "innerHTML swap:200ms settle:1s transition:false ignoreTitle:true"
+        );
+
+        claims::assert_err!("innerHTML transition:maybe".parse::<SwapStyle>());
+    }
+
+    #[test]
+    fn effective_scroll_precedence() {
+        let top = ScrollModifier {
+            selector: None,
+            position: ScrollPosition::Top,
+        };
+        let window_bottom = ScrollModifier {
+            selector: Some("window".into()),
+            position: ScrollPosition::Bottom,
+        };
+
+        let style: SwapStyle = "innerHTML focus-scroll:false scroll:top".parse().unwrap();
+        assert_eq!(style.scroll, Some(top.clone()));
+        assert_eq!(style.focus_scroll, Some(false));
+        assert_eq!(style.effective_scroll(true), ScrollBehavior::Scroll(top));
+
+        let style: SwapStyle = "innerHTML scroll:top show:window:bottom".parse().unwrap();
+        assert_eq!(
+            style.effective_scroll(false),
+            ScrollBehavior::Show(window_bottom)
+        );
+
+        let style: SwapStyle = "innerHTML focus-scroll:true".parse().unwrap();
+        assert_eq!(
+            style.effective_scroll(false),
+            ScrollBehavior::FocusedElement
+        );
+
+        let style: SwapStyle = "innerHTML focus-scroll:false".parse().unwrap();
+        assert_eq!(style.effective_scroll(true), ScrollBehavior::None);
+        assert_eq!(
+            SwapStyle::new(Swap::InnerHtml).effective_scroll(true),
+            ScrollBehavior::FocusedElement
+        );
+    }
+
+    #[test]
+    fn boosted_style_shows_top() {
+        assert_eq!(
+            SwapStyle::for_boosted(),
+            "innerHTML show:top".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn boosted_link_and_form_styles() {
+        assert_eq!(SwapStyle::for_boosted_form(), SwapStyle::for_boosted());
+        assert_eq!(
+            SwapStyle::for_boosted_link("/page"),
+            SwapStyle::for_boosted()
+        );
+        assert_eq!(
+            SwapStyle::for_boosted_link("/page#section"),
+            SwapStyle::new(Swap::InnerHtml)
+        );
+        assert_ne!(
+            SwapStyle::for_boosted_link("/page#section"),
+            SwapStyle::for_boosted_form()
+        );
+    }
+
+    #[test]
+    fn show_none_round_trips() {
+        let style: SwapStyle = "innerHTML show:none".parse().unwrap();
+
+        assert_eq!(
+            style.show,
+            Some(ScrollModifier {
+                selector: None,
+                position: ScrollPosition::None,
+            })
+        );
+        assert_ne!(style, SwapStyle::new(Swap::InnerHtml));
+        assert_eq!(style.to_string(), "innerHTML show:none");
+        assert_eq!(style.effective_scroll(false), ScrollBehavior::None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn swap_style_serde_round_trip() {
+        let style = SwapStyle {
+            swap: Some(Duration::from_millis(200)),
+            ..SwapStyle::new(Swap::OuterHtml)
+        };
+
+        let json = serde_json::to_string(&style).unwrap();
+        assert_eq!(json, r#""outerHTML swap:200ms""#);
+        assert_eq!(serde_json::from_str::<SwapStyle>(&json).unwrap(), style);
+
+        claims::assert_err!(serde_json::from_str::<SwapStyle>(r#""sideways""#));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn swap_style_deserializes_from_object() {
+        let style = SwapStyle {
+            swap: Some(Duration::from_millis(200)),
+            ..SwapStyle::new(Swap::OuterHtml)
+        };
+
+        claims::assert_ok_eq!(
+            serde_json::from_str::<SwapStyle>(r#"{"style":"outerHTML","modifiers":"swap:200ms"}"#),
+            style
+        );
+        claims::assert_ok_eq!(
+            serde_json::from_str::<SwapStyle>(r#"{"style":"beforeend"}"#),
+            SwapStyle::new(Swap::BeforeEnd)
+        );
+        claims::assert_err!(serde_json::from_str::<SwapStyle>(
+            r#"{"modifiers":"swap:1s"}"#
+        ));
+    }
+
+    #[test]
+    fn swap_undo_hints() {
+        let hints = [
+            (Swap::InnerHtml, UndoHint::RestoreChildren),
+            (Swap::OuterHtml, UndoHint::RestoreElement),
+            (Swap::TextContent, UndoHint::RestoreChildren),
+            (Swap::BeforeBegin, UndoHint::RemovePreviousSiblings),
+            (Swap::AfterBegin, UndoHint::RemoveFirstChildren),
+            (Swap::BeforeEnd, UndoHint::RemoveLastChildren),
+            (Swap::AfterEnd, UndoHint::RemoveNextSiblings),
+            (Swap::Delete, UndoHint::ReinsertElement),
+            (Swap::None, UndoHint::Nothing),
+        ];
+
+        for (swap, hint) in hints {
+            assert_eq!(swap.undo_hint(), hint, "{swap:?}");
+        }
+    }
+
+    #[test]
+    fn swap_response_shapes() {
+        let shapes = [
+            (Swap::InnerHtml, ResponseShape::Fragment),
+            (Swap::OuterHtml, ResponseShape::WholeElement),
+            (Swap::TextContent, ResponseShape::Fragment),
+            (Swap::BeforeBegin, ResponseShape::Fragment),
+            (Swap::AfterBegin, ResponseShape::Fragment),
+            (Swap::BeforeEnd, ResponseShape::Fragment),
+            (Swap::AfterEnd, ResponseShape::Fragment),
+            (Swap::Delete, ResponseShape::NoBody),
+            (Swap::None, ResponseShape::NoBody),
+        ];
+
+        for (swap, shape) in shapes {
+            assert_eq!(swap.response_shape(), shape, "{swap:?}");
+            assert_eq!(swap.requires_body(), shape != ResponseShape::NoBody);
+        }
+    }
+
+    #[test]
+    fn swap_uses_htmx_casing() {
+        assert_eq!(HeaderValue::from(Swap::InnerHtml), "innerHTML");
+        assert_eq!(HeaderValue::from(Swap::OuterHtml), "outerHTML");
+        claims::assert_ok_eq!(Swap::try_from(&b"innerHTML"[..]), Swap::InnerHtml);
+        claims::assert_ok_eq!(Swap::try_from(&b"outerHTML"[..]), Swap::OuterHtml);
+
+        // the old spelling is still accepted on decode.
+        claims::assert_ok_eq!(Swap::try_from(&b"innerHtml"[..]), Swap::InnerHtml);
+        claims::assert_ok_eq!(Swap::try_from(&b"outerHtml"[..]), Swap::OuterHtml);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn swap_serde_uses_htmx_casing() {
+        assert_eq!(
+            serde_json::to_string(&Swap::InnerHtml).unwrap(),
+            r#""innerHTML""#
+        );
+        claims::assert_ok_eq!(
+            serde_json::from_str::<Swap>(r#""outerHtml""#),
+            Swap::OuterHtml
+        );
+    }
+
+    #[test]
+    fn text_content_round_trips() {
+        let value = HeaderValue::from(Swap::TextContent);
+        assert_eq!(value, "textContent");
+        claims::assert_ok_eq!(Swap::try_from(value.as_bytes()), Swap::TextContent);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn text_content_deserializes() {
+        claims::assert_ok_eq!(
+            serde_json::from_str::<Swap>(r#""textContent""#),
+            Swap::TextContent
+        );
+    }
+
+    #[test]
+    fn swap_all_round_trips_with_wildcard_match() {
+        // downstream crates must match `Swap` with a wildcard arm.
+        fn describe(swap: Swap) -> &'static str {
+            match swap {
+                Swap::Delete => "removes the target",
+                Swap::None => "leaves the target",
+                _ => "swaps content",
+            }
+        }
+
+        for &swap in Swap::all() {
+            claims::assert_ok_eq!(Swap::try_from(swap.as_str().as_bytes()), swap);
+            assert_eq!(
+                describe(swap) == "swaps content",
+                swap.requires_body(),
+                "{swap:?}"
+            );
+        }
+
+        assert_eq!(Swap::all().len(), 9);
+    }
+
+    #[test]
+    fn swap_parse_error_names_value() {
+        claims::assert_ok_eq!("beforeend".parse::<Swap>(), Swap::BeforeEnd);
+
+        let err = claims::assert_err!("sideways".parse::<Swap>());
+        assert_eq!(err.bytes(), b"sideways");
+        assert_eq!(err.to_string(), "invalid swap style: `sideways`");
+
+        let err = claims::assert_err!(Swap::try_from(&b"inner\xffHTML"[..]));
+        assert_eq!(err.bytes(), b"inner\xffHTML");
+    }
+
+    #[test]
+    fn swap_style_to_header_value() {
+        let plain = SwapStyle::new(Swap::BeforeEnd);
+        claims::assert_ok_eq!(HeaderValue::try_from(&plain), "beforeend");
+        assert_eq!(HeaderValue::from(plain.style), "beforeend");
+
+        let style = SwapStyle {
+            settle: Some(Duration::from_secs(1)),
+            show: Some(ScrollModifier {
+                selector: Some(CssSelector::from("#list")),
+                position: ScrollPosition::Bottom,
+            }),
+            ..plain
+        };
+        claims::assert_ok_eq!(
+            HeaderValue::try_from(&style),
+            "beforeend settle:1s show:#list:bottom"
+        );
+
+        let unsafe_selector = SwapStyle {
+            show: Some(ScrollModifier {
+                selector: Some(CssSelector::from("#list\n")),
+                position: ScrollPosition::Top,
+            }),
+            ..SwapStyle::new(Swap::BeforeEnd)
+        };
+        claims::assert_err!(HeaderValue::try_from(&unsafe_selector));
+    }
+
+    #[test]
+    fn swap_attr_values_need_no_escaping() {
+        for &swap in Swap::all() {
+            let value = swap.as_attr_safe();
+            assert!(
+                value.bytes().all(|b| b.is_ascii_alphabetic()),
+                "{value:?} needs escaping"
+            );
+        }
+    }
+
+    #[test]
+    fn swap_style_tolerates_whitespace_runs() {
+        let expected = SwapStyle {
+            swap: Some(Duration::from_millis(200)),
+            settle: Some(Duration::from_secs(1)),
+            ..SwapStyle::new(Swap::OuterHtml)
+        };
+
+        for s in [
+            "outerHTML  swap:200ms   settle:1s",
+            "outerHTML\tswap:200ms\t\tsettle:1s",
+            "  outerHTML \t swap:200ms\nsettle:1s  ",
+        ] {
+            assert_eq!(s.parse(), Ok(expected.clone()), "{s:?}");
+            assert_eq!(SwapStyle::from_str_strict(s), Ok(expected.clone()), "{s:?}");
+        }
+
+        assert_eq!(expected.to_string(), "outerHTML swap:200ms settle:1s");
     }
 }